@@ -2,15 +2,35 @@ use std::sync::{Mutex, Arc};
 
 use crate::{
     frontend::{
-        ast::ast_struct::ASTNode, 
+        ast::{ast_struct::ASTNode, syntax_element::MatchArm}, 
         symbol_table::symbol_table_struct::SymbolTableStack
     },
-    backend::{codegen::ir::ir_codegen_core::IRGenerator, llvm_lib::ir_lib::{init_ir::create_basic_block, utils::position_builder, element::{create_cond_br, create_br}}}, 
+    backend::{codegen::ir::ir_codegen_core::IRGenerator, llvm_lib::ir_lib::{init_ir::create_basic_block, utils::{position_builder, has_terminator}, element::{create_cond_br, create_br, create_switch, add_switch_case}}}, 
 };
 
-use llvm::prelude::LLVMValueRef;
+use llvm::prelude::{LLVMBasicBlockRef, LLVMValueRef};
 
 impl IRGenerator {
+    /// Generates LLVM IR for the statements of a block in their own variable scope.
+    ///     Statements after a return are unreachable and are skipped
+    pub fn generate_block_ir(&mut self, nodes: &[ASTNode], symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) {
+        self.push_scope();
+        for node in nodes.iter() {
+            if has_terminator(self.get_current_block()) {
+                break;
+            }
+            self.ir_router(node, symbol_table_stack);
+        }
+        self.pop_scope();
+    }
+
+    /// Branches to a block unless the current block has already been terminated, e.g. by a return
+    fn branch_if_open(&mut self, target_bb: LLVMBasicBlockRef) {
+        if !has_terminator(self.get_current_block()) {
+            create_br(self.get_builder(), target_bb);
+        }
+    }
+
     /// Generates LLVM IR for a do while loop
     pub fn generate_do_while_ir(&mut self, body: &Box<Vec<ASTNode>>, condition: &Box<ASTNode>, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> LLVMValueRef {
         let function = self.get_current_function();
@@ -23,10 +43,10 @@ impl IRGenerator {
         create_br(self.get_builder(), do_body_bb);
     
         position_builder(self.get_builder(), do_body_bb);
-        for node in body.iter() {
-            self.ir_router(node, symbol_table_stack);
-        }
-        create_br(self.get_builder(), do_cond_bb);
+        self.push_loop(do_cond_bb, do_end_bb);
+        self.generate_block_ir(body, symbol_table_stack);
+        self.pop_loop();
+        self.branch_if_open(do_cond_bb);
     
         position_builder(self.get_builder(), do_cond_bb);
         let condition_val = self.ir_router(condition, symbol_table_stack);
@@ -52,15 +72,11 @@ impl IRGenerator {
         let condition_val = self.ir_router(condition, symbol_table_stack);
         create_cond_br(self.get_builder(), condition_val, while_body_bb, while_end_bb);
     
-        let body_returns = body.last().map_or(false, |node| node.is_return());
-    
         position_builder(self.get_builder(), while_body_bb);
-        for node in body.iter() {
-            self.ir_router(node, symbol_table_stack);
-        }
-        if !body_returns {
-            create_br(self.get_builder(), while_cond_bb);
-        }
+        self.push_loop(while_cond_bb, while_end_bb);
+        self.generate_block_ir(body, symbol_table_stack);
+        self.pop_loop();
+        self.branch_if_open(while_cond_bb);
     
         position_builder(self.get_builder(), while_end_bb);
     
@@ -78,6 +94,8 @@ impl IRGenerator {
         let entry_bb = self.get_current_block();
         position_builder(self.get_builder(), entry_bb);
 
+        // the initializer's variable is scoped to the loop
+        self.push_scope();
         if let Some(init_node) = initializer {
             self.ir_router(init_node, symbol_table_stack);
         }
@@ -88,23 +106,21 @@ impl IRGenerator {
         create_cond_br(self.get_builder(), condition_val, for_body_bb, for_end_bb);
 
         position_builder(self.get_builder(), for_body_bb);
-        let body_returns = body.last().map_or(false, |node| node.is_return());
-        for node in body.iter() {
-            self.ir_router(node, symbol_table_stack);
-        }
+        self.push_loop(for_inc_bb.unwrap_or(for_cond_bb), for_end_bb);
+        self.generate_block_ir(body, symbol_table_stack);
+        self.pop_loop();
 
         if let Some(inc_bb) = for_inc_bb {
-            if !body_returns {
-                create_br(self.get_builder(), inc_bb);
-            }
+            self.branch_if_open(inc_bb);
             position_builder(self.get_builder(), inc_bb);
             if let Some(inc_node) = increment {
                 self.ir_router(inc_node, symbol_table_stack);
             }
             create_br(self.get_builder(), for_cond_bb);
-        } else if !body_returns {
-            create_br(self.get_builder(), for_cond_bb);
+        } else {
+            self.branch_if_open(for_cond_bb);
         }
+        self.pop_scope();
 
         position_builder(self.get_builder(), for_end_bb);
 
@@ -122,43 +138,55 @@ impl IRGenerator {
         } else {
             None
         };
+        // if both branches return, the merge block has no predecessors and is left for the caller to terminate
+        let merge_bb = create_basic_block(self.get_context(), function, "merge");
     
-        let then_returns = then_branch.last().map_or(false, |node| node.is_return());
-        let else_returns = else_branch.as_ref().map_or(true, |branch| {
-            branch.last().map_or(false, |node| node.is_return())
-        });
-    
-        let merge_bb_needed = !then_returns || !else_returns;
-        let merge_bb = if merge_bb_needed {
-            Some(create_basic_block(self.get_context(), function, "merge"))
-        } else {
-            None
-        };
-    
-        create_cond_br(self.get_builder(), condition_val, then_bb, else_bb.unwrap_or_else(|| merge_bb.unwrap_or(then_bb)));
+        create_cond_br(self.get_builder(), condition_val, then_bb, else_bb.unwrap_or(merge_bb));
     
         position_builder(self.get_builder(), then_bb);
-        for node in then_branch.iter() {
-            self.ir_router(node, symbol_table_stack);
-        }
-        if merge_bb_needed && !then_returns {
-            create_br(self.get_builder(), merge_bb.unwrap());
+        self.generate_block_ir(then_branch, symbol_table_stack);
+        self.branch_if_open(merge_bb);
+    
+        if let (Some(else_nodes), Some(else_bb)) = (else_branch, else_bb) {
+            position_builder(self.get_builder(), else_bb);
+            self.generate_block_ir(else_nodes, symbol_table_stack);
+            self.branch_if_open(merge_bb);
         }
     
-        if let Some(else_nodes) = else_branch {
-            position_builder(self.get_builder(), else_bb.unwrap());
-            for node in else_nodes.iter() {
-                self.ir_router(node, symbol_table_stack);
-            }
-            if merge_bb_needed && !else_returns {
-                create_br(self.get_builder(), merge_bb.unwrap());
+        position_builder(self.get_builder(), merge_bb);
+    
+        std::ptr::null_mut()
+    }
+
+    /// Generates LLVM IR for a match statement as a switch on the matched value. Each arm gets its own block, the 
+    ///     wildcard arm is the switch's default, and every arm that doesn't return continues at a shared merge block
+    pub fn generate_match_ir(&mut self, to_match: &ASTNode, arms: &[MatchArm], symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> LLVMValueRef {
+        let function = self.get_current_function();
+        let value = self.ir_router(to_match, symbol_table_stack);
+
+        let arm_bbs: Vec<LLVMBasicBlockRef> = arms.iter()
+            .map(|arm| create_basic_block(self.get_context(), function, if arm.is_wildcard() { "match_default" } else { "match_arm" }))
+            .collect();
+        let match_end_bb = create_basic_block(self.get_context(), function, "match_end");
+
+        let default_bb = arms.iter().position(MatchArm::is_wildcard).map_or(match_end_bb, |index| arm_bbs[index]);
+        let num_cases = arms.iter().filter(|arm| !arm.is_wildcard()).count() as u32;
+        let switch = create_switch(self.get_builder(), value, default_bb, num_cases);
+        for (arm, arm_bb) in arms.iter().zip(&arm_bbs) {
+            if !arm.is_wildcard() {
+                let pattern = self.ir_router(&arm.get_variant(), symbol_table_stack);
+                add_switch_case(switch, pattern, *arm_bb);
             }
         }
-    
-        if let Some(merge) = merge_bb {
-            position_builder(self.get_builder(), merge);
+
+        for (arm, arm_bb) in arms.iter().zip(&arm_bbs) {
+            position_builder(self.get_builder(), *arm_bb);
+            self.generate_block_ir(&arm.get_action(), symbol_table_stack);
+            self.branch_if_open(match_end_bb);
         }
-    
+
+        position_builder(self.get_builder(), match_end_bb);
+
         std::ptr::null_mut()
     }
-} 
\ No newline at end of file
+}
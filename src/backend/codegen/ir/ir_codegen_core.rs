@@ -1,4 +1,4 @@
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
 use std::sync::{
     Mutex, Arc
 };
@@ -14,7 +14,7 @@ use crate::frontend::{
         ModAST,
         ModElement,
     }, 
-    ast::syntax_element::SyntaxElement, 
+    ast::{data_type::DataType, syntax_element::SyntaxElement}, 
     symbol_table::symbol_table_struct::SymbolTableStack
 };
 
@@ -24,6 +24,10 @@ pub struct IRGenerator {
     module: LLVMModuleRef,
     builder: LLVMBuilderRef,
     current_function: Option<LLVMValueRef>,
+    named_values: Vec<HashMap<String, (LLVMValueRef, LLVMTypeRef, DataType)>>, // scoped stack slots of local variables and their types
+    loop_stack: Vec<(LLVMBasicBlockRef, LLVMBasicBlockRef)>, // continue and break targets of the enclosing loops
+    enums: HashMap<String, Vec<String>>, // variants of each declared enum, in tag order
+    structs: HashMap<String, (LLVMTypeRef, Vec<String>)>, // named type and field names of each declared struct, in field order
 }
 
 impl IRGenerator {
@@ -43,7 +47,11 @@ impl IRGenerator {
                 context,
                 module,
                 builder,
-                current_function: None
+                current_function: None,
+                named_values: Vec::new(),
+                loop_stack: Vec::new(),
+                enums: HashMap::new(),
+                structs: HashMap::new(),
             }
         }
     }
@@ -68,6 +76,56 @@ impl IRGenerator {
     pub fn get_builder(&self) -> LLVMBuilderRef {
         self.builder
     }
+    /// Opens a new scope for local variables
+    pub fn push_scope(&mut self) {
+        self.named_values.push(HashMap::new())
+    }
+    /// Closes the innermost scope, dropping the local variables declared in it
+    pub fn pop_scope(&mut self) {
+        self.named_values.pop();
+    }
+    /// Adds a local variable's stack slot and type to the innermost scope
+    pub fn add_named_value(&mut self, name: &str, slot: LLVMValueRef, ty: LLVMTypeRef, data_type: &DataType) {
+        self.named_values.last_mut()
+            .expect("No scope to add a variable to")
+            .insert(name.to_string(), (slot, ty, data_type.clone()));
+    }
+    /// Retrieves a local variable's stack slot and type, searching from the innermost scope outwards
+    pub fn get_named_value(&self, name: &str) -> Option<(LLVMValueRef, LLVMTypeRef)> {
+        self.named_values.iter().rev().find_map(|scope| scope.get(name).map(|(slot, ty, _)| (*slot, *ty)))
+    }
+    /// Retrieves the data type a local variable was declared with, searching from the innermost scope outwards
+    pub fn get_named_value_type(&self, name: &str) -> Option<DataType> {
+        self.named_values.iter().rev().find_map(|scope| scope.get(name).map(|(_, _, data_type)| data_type.clone()))
+    }
+    /// Enters a loop body, recording where continue and break statements inside it branch to
+    pub fn push_loop(&mut self, continue_bb: LLVMBasicBlockRef, break_bb: LLVMBasicBlockRef) {
+        self.loop_stack.push((continue_bb, break_bb))
+    }
+    /// Leaves the innermost loop body
+    pub fn pop_loop(&mut self) {
+        self.loop_stack.pop();
+    }
+    /// Retrieves the continue and break targets of the innermost loop
+    pub fn get_current_loop(&self) -> Option<(LLVMBasicBlockRef, LLVMBasicBlockRef)> {
+        self.loop_stack.last().copied()
+    }
+    /// Records the variants of an enum, in tag order
+    pub fn add_enum(&mut self, name: &str, variants: &[String]) {
+        self.enums.insert(name.to_string(), variants.to_vec());
+    }
+    /// Retrieves the variants of an enum, if it has been declared
+    pub fn get_enum_variants(&self, name: &str) -> Option<&Vec<String>> {
+        self.enums.get(name)
+    }
+    /// Records the named type and field names of a struct, in field order
+    pub fn add_struct(&mut self, name: &str, struct_type: LLVMTypeRef, fields: &[String]) {
+        self.structs.insert(name.to_string(), (struct_type, fields.to_vec()));
+    }
+    /// Retrieves the named type and field names of a struct, if it has been declared
+    pub fn get_struct(&self, name: &str) -> Option<&(LLVMTypeRef, Vec<String>)> {
+        self.structs.get(name)
+    }
     /// Retrieves the current insert block
     pub fn get_current_block(&self) -> LLVMBasicBlockRef {
         unsafe {
@@ -81,6 +139,32 @@ impl IRGenerator {
 
         let module: &mut BinaryHeap<ModElement> = input.get_children();
 
+        // declare every type and function up front so they can be used before the point they are defined in the file
+        let top_level_nodes: Vec<ASTNode> = module.iter()
+            .flat_map(|mod_element| {
+                let root: ASTNode = mod_element.get_ast().get_root();
+                std::iter::once(root.clone()).chain(root.get_children())
+            })
+            .collect();
+        // structs are created opaque before any of them gets a body, so fields can be of any struct type
+        for node in &top_level_nodes {
+            match node.get_element() {
+                SyntaxElement::EnumDeclaration { name, variants } => ir_generator.add_enum(&name, &variants),
+                SyntaxElement::StructDeclaration { name, fields } => { ir_generator.declare_struct_type(&name, &fields); },
+                _ => {},
+            }
+        }
+        for node in &top_level_nodes {
+            if let SyntaxElement::StructDeclaration { name, fields } = node.get_element() {
+                ir_generator.define_struct_body(&name, &fields);
+            }
+        }
+        for node in &top_level_nodes {
+            if let SyntaxElement::FunctionDeclaration { name, parameters, return_type } = node.get_element() {
+                ir_generator.declare_fn_prototype(&name, &parameters, &return_type);
+            }
+        }
+
         while let Some(mod_element) = module.pop() {
             let ast: AST = mod_element.get_ast();
             let symbol_table_stack: Arc<Mutex<SymbolTableStack>> = mod_element.get_sym_table_stack();
@@ -132,16 +216,16 @@ impl IRGenerator {
                 self.generate_binary_exp_ir(left, operator, right, sym_table_stack)    
             },
             SyntaxElement::MatchStatement { to_match, arms } => {
-                self.generate_match_ir(to_match, arms)
+                self.generate_match_ir(to_match, arms, sym_table_stack)
             },
             SyntaxElement::FunctionCall { name, arguments } => {
-                self.generate_fn_call_ir(name, arguments)
+                self.generate_fn_call_ir(name, arguments, sym_table_stack)
             },
             SyntaxElement::Initialization { variable, data_type, value } => {
-                self.generate_initialization_ir(variable, data_type, value)
+                self.generate_initialization_ir(variable, data_type, value, sym_table_stack)
             },
             SyntaxElement::Assignment { variable, value } => {
-                self.generate_assignment_ir(variable, value)
+                self.generate_assignment_ir(variable, value, sym_table_stack)
             },
             SyntaxElement::UnaryExpression { operator, operand } => {
                 self.generate_unary_ir(operator, operand, sym_table_stack)
@@ -149,14 +233,32 @@ impl IRGenerator {
             SyntaxElement::Return { value } => {
                 self.generate_return_ir(value, sym_table_stack)
             },
+            SyntaxElement::Break => {
+                self.generate_break_ir()
+            },
+            SyntaxElement::Continue => {
+                self.generate_continue_ir()
+            },
             
             // primitive
             SyntaxElement::Literal { data_type, value } => {
-                self.generate_literal_ir(*data_type, value.to_string())                           
+                self.generate_literal_ir(data_type.clone(), value.to_string())                           
             },
             SyntaxElement::Variable { data_type, name } => {
                 self.generate_var_ir(data_type, name)
             },
+            SyntaxElement::EnumVariant { enum_name, variant } => {
+                self.generate_enum_variant_ir(enum_name, variant)
+            },
+            SyntaxElement::StructLiteral { name, fields } => {
+                self.generate_struct_literal_ir(name, fields, sym_table_stack)
+            },
+            SyntaxElement::FieldAccess { base, field } => {
+                self.generate_field_access_ir(base, field, sym_table_stack)
+            },
+            SyntaxElement::FieldAssignment { base, field, value } => {
+                self.generate_field_assignment_ir(base, field, value, sym_table_stack)
+            },
 
             _ => panic!("Unrecognized syntax element {:?}", node)
 
@@ -1,11 +1,16 @@
-use llvm_sys::prelude::LLVMValueRef;
+use std::sync::{Arc, Mutex};
+
+use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
 
 use crate::{
     backend::{
-        llvm_lib::ir_lib::element,
+        llvm_lib::ir_lib::{element, types::{struct_field_type, struct_type_name}, utils::type_of},
         codegen::ir::ir_codegen_core::IRGenerator
     }, 
-    frontend::ast::data_type::DataType
+    frontend::{
+        ast::{ast_struct::ASTNode, data_type::DataType, syntax_element::SyntaxElement},
+        symbol_table::symbol_table_struct::SymbolTableStack,
+    },
 };
 
 impl IRGenerator {
@@ -19,6 +24,14 @@ impl IRGenerator {
                 };
                 element::create_integer(val, self.get_context()) 
             },
+            sized if sized.is_integer() => {
+                // folded constants may be negative, and are kept as their two's complement bits
+                let val: u64 = match value.parse::<u64>().or_else(|_| value.parse::<i64>().map(|val| val as u64)) {
+                    Ok(val) => val,
+                    Err(e) => panic!("Failed to parse integer: {}", e),
+                };
+                element::create_sized_integer(val, sized.integer_width().unwrap_or(64), self.get_context())
+            },
             DataType::Float => {
                 let val: f64 = match value.parse::<f64>() {
                     Ok(val) => val,
@@ -38,7 +51,14 @@ impl IRGenerator {
                     Ok(val) => val,
                     Err(e) => panic!("Failed to parse string: {}", e),
                 };
-                element::create_string(&val, self.get_builder())
+                element::create_string(&val, self.get_context(), self.get_module(), self.get_builder())
+            },
+            DataType::Char => {
+                let val: char = match value.parse::<char>() {
+                    Ok(val) => val,
+                    Err(e) => panic!("Failed to parse char: {}", e),
+                };
+                element::create_char(val, self.get_context())
             },
             DataType::Unknown => {
                 std::ptr::null_mut() // this is intentional
@@ -47,19 +67,78 @@ impl IRGenerator {
         }
     }
 
-    /// TODO
-    pub fn generate_var_ir(&mut self, data_type: &DataType, _name: &String) -> LLVMValueRef {
-        match data_type {
-            DataType::Integer => {
-                // let val: i64 = match value.parse::<i64>() {
-                //     Ok(val) => val,
-                //     Err(e) => panic!("Failed to parse integer: {}", e),
-                // };
-                // self.add_named_value(name, value)
-                // create_element::create_integer(val, self.get_context())
-                std::ptr::null_mut()
-            }
-            _ => unimplemented!("unimplemented var ir")
+    /// Generates LLVM IR for reading a variable, loading it from its stack slot
+    pub fn generate_var_ir(&mut self, _data_type: &DataType, name: &String) -> LLVMValueRef {
+        match self.get_named_value(name) {
+            Some((slot, llvm_type)) => element::create_load(self.get_builder(), llvm_type, slot, name),
+            None => panic!("Variable not found: {}", name),
+        }
+    }
+
+    /// Generates LLVM IR for an enum variant, its tag
+    pub fn generate_enum_variant_ir(&mut self, enum_name: &String, variant: &String) -> LLVMValueRef {
+        let index: usize = match self.get_enum_variants(enum_name) {
+            Some(variants) => variants.iter().position(|name| name == variant)
+                .unwrap_or_else(|| panic!("Enum {} has no variant {}", enum_name, variant)),
+            None => panic!("Enum not found: {}", enum_name),
+        };
+        element::create_enum_tag(index, self.get_context())
+    }
+
+    /// Generates LLVM IR for a struct literal, inserting each field's value in declaration order
+    pub fn generate_struct_literal_ir(&mut self, name: &str, fields: &[(String, ASTNode)], 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> LLVMValueRef {
+        let (struct_type, field_names) = match self.get_struct(name) {
+            Some((struct_type, field_names)) => (*struct_type, field_names.clone()),
+            None => panic!("Struct not found: {}", name),
+        };
+
+        let mut struct_value: LLVMValueRef = element::create_undef(struct_type);
+        for (index, field_name) in field_names.iter().enumerate() {
+            let value: &ASTNode = match fields.iter().find(|(field, _)| field == field_name) {
+                Some((_, value)) => value,
+                None => panic!("Struct {} is missing field {}", name, field_name),
+            };
+            let value_ir: LLVMValueRef = self.ir_router(value, symbol_table_stack);
+            struct_value = element::create_insert_value(self.get_builder(), struct_value, value_ir, index as u32, field_name);
+        }
+        struct_value
+    }
+
+    /// Generates LLVM IR for reading a struct field. Fields of variables are loaded through a pointer into the variable's
+    ///     stack slot, fields of any other struct value are extracted from it
+    pub fn generate_field_access_ir(&mut self, base: &ASTNode, field: &str, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> LLVMValueRef {
+        if let Some((field_pointer, field_type)) = self.generate_field_pointer(base, field) {
+            return element::create_load(self.get_builder(), field_type, field_pointer, field);
         }
+
+        let base_ir: LLVMValueRef = self.ir_router(base, symbol_table_stack);
+        let (index, _) = self.get_struct_field(type_of(base_ir), field);
+        element::create_extract_value(self.get_builder(), base_ir, index, field)
+    }
+
+    /// Generates a pointer to a struct field and the field's type, if the struct lives in a variable's stack slot
+    pub fn generate_field_pointer(&mut self, base: &ASTNode, field: &str) -> Option<(LLVMValueRef, LLVMTypeRef)> {
+        let (base_pointer, base_type) = match base.get_element() {
+            SyntaxElement::Variable { name, .. } => self.get_named_value(&name)?,
+            SyntaxElement::FieldAccess { base, field } => self.generate_field_pointer(&base, &field)?,
+            _ => return None,
+        };
+
+        let (index, field_type) = self.get_struct_field(base_type, field);
+        let field_pointer: LLVMValueRef = element::create_struct_gep(self.get_builder(), base_type, base_pointer, index, &format!("{}.ptr", field));
+        Some((field_pointer, field_type))
+    }
+
+    /// Retrieves the index and type of a field within a named struct type
+    fn get_struct_field(&self, struct_type: LLVMTypeRef, field: &str) -> (u32, LLVMTypeRef) {
+        let name: String = struct_type_name(struct_type).unwrap_or_else(|| panic!("Field access {} on a non-struct value", field));
+        let index: usize = match self.get_struct(&name) {
+            Some((_, field_names)) => field_names.iter().position(|field_name| field_name == field)
+                .unwrap_or_else(|| panic!("Struct {} has no field {}", name, field)),
+            None => panic!("Struct not found: {}", name),
+        };
+        (index as u32, struct_field_type(struct_type, index as u32))
     }
 }
\ No newline at end of file
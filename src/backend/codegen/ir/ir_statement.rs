@@ -6,28 +6,31 @@ use crate::{
         llvm_lib::ir_lib::{
             element::{
                 create_break_statement, 
-                create_continue_statement
+                create_call,
+                create_continue_statement,
+                create_entry_alloca,
+                create_store,
+                get_named_function,
             }, 
             ops, 
-            return_type::nonvoid_return,
+            return_type::{nonvoid_return, void_return},
+            types::is_float_type,
+            utils::type_of,
         }
     }, 
     frontend::{
         ast::{
             ast_struct::ASTNode, 
             data_type::DataType, 
-            syntax_element::{
-                MatchArm, 
-                SyntaxElement,
-            }
+            syntax_element::SyntaxElement,
         }, 
-        symbol_table::symbol_table_struct::SymbolTableStack,
+        symbol_table::symbol_table_struct::{SymbolTableStack, SymbolValue},
 } 
 };
 
-use llvm::prelude::LLVMValueRef;
+use llvm::{core, LLVMRealPredicate, LLVMTypeKind};
+use llvm::prelude::{LLVMTypeRef, LLVMValueRef};
 use llvm::LLVMValue;
-use llvm::LLVMBasicBlock;
 
 impl IRGenerator {
     /// Generates LLVM IR for a binary expression 
@@ -36,96 +39,176 @@ impl IRGenerator {
         let left_val: *mut LLVMValue = self.ir_router(left, symbol_table_stack);
         let right_val: *mut LLVMValue = self.ir_router(right, symbol_table_stack); 
 
+        // operands have the same type after semantic analysis, so the left one decides between integer and float 
+        //     instructions, and between signed and unsigned ones
+        let is_float: bool = is_float_type(type_of(left_val));
+        let signed: bool = !self.expression_type(left, symbol_table_stack).is_unsigned();
+        let builder = self.get_builder();
+
         match operator.as_str() {
             "+" => {
                 let tmp_name: CString = CString::new("addtmp").expect("Failed to create CString for add");
-                ops::build_add(self.get_builder(), left_val, right_val, tmp_name)
+                if is_float { ops::build_fadd(builder, left_val, right_val, tmp_name) } else { ops::build_add(builder, left_val, right_val, tmp_name) }
             }
             "-" => {
                 let tmp_name: CString = CString::new("subtmp").expect("Failed to create CString for minus");
-                ops::build_sub(self.get_builder(), left_val, right_val, tmp_name)
+                if is_float { ops::build_fsub(builder, left_val, right_val, tmp_name) } else { ops::build_sub(builder, left_val, right_val, tmp_name) }
             }
             "/" => {
                 let tmp_name: CString = CString::new("divtmp").expect("Failed to create CString for divide");
-                ops::build_div(self.get_builder(), left_val, right_val, tmp_name)
+                if is_float { ops::build_fdiv(builder, left_val, right_val, tmp_name) } else { ops::build_div(builder, signed, left_val, right_val, tmp_name) }
             }
             "*" => {
                 let tmp_name: CString = CString::new("multmp").expect("Failed to create CString for divide");
-                ops::build_mul(self.get_builder(), left_val, right_val, tmp_name)
+                if is_float { ops::build_fmul(builder, left_val, right_val, tmp_name) } else { ops::build_mul(builder, left_val, right_val, tmp_name) }
             }
             "%" => {
                 let tmp_name: CString = CString::new("remtmp").expect("Failed to create CString for divide");
-                ops::build_rem(self.get_builder(), left_val, right_val, tmp_name)
+                if is_float { ops::build_frem(builder, left_val, right_val, tmp_name) } else { ops::build_rem(builder, signed, left_val, right_val, tmp_name) }
             }
             "&&" => {
                 let tmp_name: CString = CString::new("andtmp").expect("Failed to create CString for divide");
-                ops::build_and(self.get_builder(), left_val, right_val, tmp_name)
+                ops::build_and(builder, left_val, right_val, tmp_name)
             }
-            "|" => {
+            "||" => {
                 let tmp_name: CString = CString::new("ortmp").expect("Failed to create CString for divide");
-                ops::build_or(self.get_builder(), left_val, right_val, tmp_name)
+                ops::build_or(builder, left_val, right_val, tmp_name)
             }
             "^" => {
                 let tmp_name: CString = CString::new("xortmp").expect("Failed to create CString for divide");
-                ops::build_xor(self.get_builder(), left_val, right_val, tmp_name)
+                ops::build_xor(builder, left_val, right_val, tmp_name)
             }
             "<<" => {
                 let tmp_name: CString = CString::new("shltmp").expect("Failed to create CString for shift left");
-                ops::build_shl(self.get_builder(), left_val, right_val, tmp_name)
+                ops::build_shl(builder, left_val, right_val, tmp_name)
             }
             ">>" => {
                 let tmp_name: CString = CString::new("shrtmp").expect("Failed to create CString for shift right");
-                ops::build_shr(self.get_builder(), left_val, right_val, tmp_name)
+                ops::build_shr(builder, signed, left_val, right_val, tmp_name)
             }
             ">" => {
                 let tmp_name: CString = CString::new("gttmp").expect("Failed to create CString for greater than");
-                ops::build_icmp_gt(self.get_builder(), left_val, right_val, tmp_name)
+                if is_float { ops::build_fcmp(builder, LLVMRealPredicate::LLVMRealOGT, left_val, right_val, tmp_name) } 
+                else { ops::build_icmp_gt(builder, signed, left_val, right_val, tmp_name) }
             }
             "<" => {
                 let tmp_name: CString = CString::new("lttmp").expect("Failed to create CString for less than");
-                ops::build_icmp_lt(self.get_builder(), left_val, right_val, tmp_name)
+                if is_float { ops::build_fcmp(builder, LLVMRealPredicate::LLVMRealOLT, left_val, right_val, tmp_name) } 
+                else { ops::build_icmp_lt(builder, signed, left_val, right_val, tmp_name) }
             }
             "==" => {
                 let tmp_name: CString = CString::new("eqtmp").expect("Failed to create CString for equal");
-                ops::build_icmp_eq(self.get_builder(), left_val, right_val, tmp_name)
+                if is_float { ops::build_fcmp(builder, LLVMRealPredicate::LLVMRealOEQ, left_val, right_val, tmp_name) } 
+                else { ops::build_icmp_eq(builder, left_val, right_val, tmp_name) }
+            }
+            "!=" => {
+                let tmp_name: CString = CString::new("netmp").expect("Failed to create CString for not equal");
+                if is_float { ops::build_fcmp(builder, LLVMRealPredicate::LLVMRealONE, left_val, right_val, tmp_name) } 
+                else { ops::build_icmp_ne(builder, left_val, right_val, tmp_name) }
+            }
+            "<=" => {
+                let tmp_name: CString = CString::new("letmp").expect("Failed to create CString for less than or equal");
+                if is_float { ops::build_fcmp(builder, LLVMRealPredicate::LLVMRealOLE, left_val, right_val, tmp_name) } 
+                else { ops::build_icmp_le(builder, signed, left_val, right_val, tmp_name) }
+            }
+            ">=" => {
+                let tmp_name: CString = CString::new("getmp").expect("Failed to create CString for greater than or equal");
+                if is_float { ops::build_fcmp(builder, LLVMRealPredicate::LLVMRealOGE, left_val, right_val, tmp_name) } 
+                else { ops::build_icmp_ge(builder, signed, left_val, right_val, tmp_name) }
             }
             _ => panic!("Unrecognized binops operator{:?}", operator.as_str()) 
         } 
     }
 
-    /// TODO
-    pub fn generate_match_ir(&mut self, _to_match: &Box<ASTNode>, _arms: &Vec<MatchArm>)-> LLVMValueRef {
-        std::ptr::null_mut()
 
-    }
+    /// Generates LLVM IR for a function call. The callee is looked up in the module, or declared from 
+    ///     its symbol table entry if it hasn't been added yet
+    pub fn generate_fn_call_ir(&mut self, name: &str, arguments: &[ASTNode], 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> LLVMValueRef {
+        let function: LLVMValueRef = match get_named_function(self.get_module(), name) {
+            Some(function) => function,
+            None => {
+                let symbol_value: Option<SymbolValue> = symbol_table_stack.lock().unwrap().lookup(name).map(|info| info.get_value());
+                match symbol_value {
+                    Some(SymbolValue::FunctionValue { parameters, return_type }) => 
+                        self.declare_fn_prototype(name, &parameters, &return_type),
+                    _ => panic!("Call to undefined function: {}", name),
+                }
+            }
+        };
 
-    /// TODO
-    pub fn generate_fn_call_ir(&mut self, _name: &String, _arguments: &Vec<ASTNode>)-> LLVMValueRef {
-        std::ptr::null_mut()
+        let param_count: usize = unsafe { core::LLVMCountParams(function) } as usize;
+        if param_count != arguments.len() {
+            panic!("Function {} expects {} arguments but {} were given", name, param_count, arguments.len());
+        }
 
-    }
-
-    /// TODO
-    pub fn generate_initialization_ir(&mut self, _variable: &String, _data_type: &DataType, _value: &Box<ASTNode>)-> LLVMValueRef {
-        std::ptr::null_mut()
+        let mut llvm_arguments: Vec<LLVMValueRef> = arguments.iter()
+            .map(|argument| self.ir_router(argument, symbol_table_stack))
+            .collect();
 
+        // void calls can't be named
+        let returns_void: bool = unsafe {
+            core::LLVMGetTypeKind(core::LLVMGetReturnType(core::LLVMGlobalGetValueType(function))) == LLVMTypeKind::LLVMVoidTypeKind
+        };
+        let tmp_name: CString = CString::new(if returns_void { "" } else { "calltmp" }).expect("Failed to create CString for call");
+        create_call(self.get_builder(), function, &mut llvm_arguments, tmp_name)
     }
 
-    /// TODO
-    pub fn generate_assignment_ir(&mut self, _variable: &String, _value: &Box<ASTNode>)-> LLVMValueRef {
-        std::ptr::null_mut()
+    /// Generates LLVM IR for a variable initialization. The variable gets a stack slot in the entry block 
+    ///     of the current function, which the initial value is stored into
+    pub fn generate_initialization_ir(&mut self, variable: &str, data_type: &DataType, value: &Box<ASTNode>, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>)-> LLVMValueRef {
+        let value_ir: LLVMValueRef = self.ir_router(value, symbol_table_stack);
+        // without an annotation or semantic analysis to resolve one, the slot takes the type of its value
+        let data_type: DataType = match data_type {
+            DataType::Unknown => self.expression_type(value, symbol_table_stack),
+            data_type => data_type.clone(),
+        };
+        let llvm_type: LLVMTypeRef = match data_type {
+            DataType::Unknown => type_of(value_ir),
+            ref data_type => self.map_data_type(data_type),
+        };
+
+        let slot: LLVMValueRef = create_entry_alloca(self.get_context(), self.get_current_function(), llvm_type, variable);
+        create_store(self.get_builder(), value_ir, slot);
+        self.add_named_value(variable, slot, llvm_type, &data_type);
+        slot
+    }
 
+    /// Generates LLVM IR for an assignment to an initialized variable
+    pub fn generate_assignment_ir(&mut self, variable: &str, value: &Box<ASTNode>, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>)-> LLVMValueRef {
+        let value_ir: LLVMValueRef = self.ir_router(value, symbol_table_stack);
+        let (slot, _) = match self.get_named_value(variable) {
+            Some(named_value) => named_value,
+            None => panic!("Assignment to undefined variable: {}", variable),
+        };
+        create_store(self.get_builder(), value_ir, slot)
     }
 
-    /// Generates LLVM IR for a break statement
-    pub fn generate_break_ir(&mut self, break_block: *mut LLVMBasicBlock ) {
-        create_break_statement(self.get_builder(), break_block)
+    /// Generates LLVM IR for an assignment to a field of a struct held in a variable
+    pub fn generate_field_assignment_ir(&mut self, base: &ASTNode, field: &str, value: &ASTNode, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>)-> LLVMValueRef {
+        let value_ir: LLVMValueRef = self.ir_router(value, symbol_table_stack);
+        let (field_pointer, _) = match self.generate_field_pointer(base, field) {
+            Some(field_pointer) => field_pointer,
+            None => panic!("Assignment to field {} of a temporary value", field),
+        };
+        create_store(self.get_builder(), value_ir, field_pointer)
     }
 
-    /// Generates LLVM IR for a continue statement
-    pub fn generate_continue_ir(&mut self, continue_block: *mut LLVMBasicBlock) {
-        create_continue_statement(self.get_builder(), continue_block)
+    /// Generates LLVM IR for a break statement, branching out of the innermost loop
+    pub fn generate_break_ir(&mut self) -> LLVMValueRef {
+        let (_, break_block) = self.get_current_loop().expect("Break statement outside of a loop");
+        create_break_statement(self.get_builder(), break_block);
+        std::ptr::null_mut()
+    }
 
+    /// Generates LLVM IR for a continue statement, branching to the next iteration of the innermost loop
+    pub fn generate_continue_ir(&mut self) -> LLVMValueRef {
+        let (continue_block, _) = self.get_current_loop().expect("Continue statement outside of a loop");
+        create_continue_statement(self.get_builder(), continue_block);
+        std::ptr::null_mut()
     }
 
     /// Generates LLVM IR for a unary operation 
@@ -134,7 +217,11 @@ impl IRGenerator {
         match operator.as_str() {
             "-" => {
                 let tmp_name = CString::new("negtmp").expect("Failed to create CString for negation");
-                ops::build_negation(self.get_builder(), operand_ir, tmp_name)
+                if is_float_type(type_of(operand_ir)) {
+                    ops::build_fnegation(self.get_builder(), operand_ir, tmp_name)
+                } else {
+                    ops::build_negation(self.get_builder(), operand_ir, tmp_name)
+                }
             },
             "~" => {
                 let tmp_name = CString::new("nottmp").expect("Failed to create CString for bitwise not");
@@ -148,31 +235,12 @@ impl IRGenerator {
         }
     }
 
-    /// TODO
+    /// Generates LLVM IR for a return statement
     pub fn generate_return_ir(&mut self, value: &Box<ASTNode>, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> LLVMValueRef {
-        if let Some(symbol_table_arc) = symbol_table_stack.lock().unwrap().peek() {
-            let symbol_table = symbol_table_arc.lock().unwrap();
-    
-            match value.get_element() {
-                SyntaxElement::Variable { name, .. } => {
-                    match symbol_table.get(&name) {
-                        Some(symbol_info) => {
-                            let llvm_val: LLVMValueRef = match &symbol_info.get_value() {
-                                _ => {panic!("unipmlemented")}
-                            };
-    
-                            nonvoid_return(self.get_builder(), llvm_val)
-                        },
-                        None => panic!("Variable not found in symbol table: {}", name),
-                    }
-                },
-                _ => {
-                    let val: *mut LLVMValue = self.ir_router( value, symbol_table_stack);
-                    nonvoid_return(self.get_builder(), val)
-                }
-            }
-        } else {
-            panic!("No symbol table found in the stack");
+        if value.get_element() == SyntaxElement::NoExpression {
+            return void_return(self.get_builder());
         }
+        let val: *mut LLVMValue = self.ir_router(value, symbol_table_stack);
+        nonvoid_return(self.get_builder(), val)
     }
 }
\ No newline at end of file
@@ -4,8 +4,11 @@ use crate::{
     backend::{
         codegen::ir::ir_codegen_core::IRGenerator, 
         llvm_lib::ir_lib::{
-            types::void_type, 
-            element::{create_function_type, add_function_to_module}, init_ir::create_basic_block, utils::position_builder
+            types::{void_type, named_struct_type, set_struct_body, is_opaque_struct}, 
+            element::{create_function_type, add_function_to_module, get_named_function, create_entry_alloca, create_store, create_unreachable}, 
+            init_ir::create_basic_block, 
+            return_type::void_return,
+            utils::{position_builder, get_param, set_value_name, has_terminator},
         }
     }, 
     frontend::{ast::{
@@ -15,18 +18,58 @@ use crate::{
     symbol_table::symbol_table_struct::SymbolTableStack}, 
 };
 
-use llvm::prelude::LLVMValueRef;
+use llvm::prelude::{LLVMTypeRef, LLVMValueRef};
 
 impl IRGenerator {
     /// Generates LLVM IR for a function declaration
     pub fn generate_fn_declaration_ir(
             &mut self, 
             name: &String, 
-            parameters: &Vec<FunctionParameter>, 
+            parameters: &[FunctionParameter], 
             return_type: &Option<DataType>,
             body: &Vec<ASTNode>,
             symbol_table_stack: &Arc<Mutex<SymbolTableStack>>,
     ) -> LLVMValueRef {
+        let function = self.declare_fn_prototype(name, parameters, return_type);
+        let entry_bb = create_basic_block(self.get_context(), function, "entry");
+        position_builder(self.get_builder(), entry_bb);
+    
+        self.set_current_function(function);
+
+        // parameters are spilled to stack slots so the body can read and reassign them like any other variable
+        self.push_scope();
+        for (index, param) in parameters.iter().enumerate() {
+            let param_value: LLVMValueRef = get_param(function, index as u32);
+            set_value_name(param_value, &param.get_name());
+
+            let param_type = self.map_data_type(&param.get_data_type());
+            let slot: LLVMValueRef = create_entry_alloca(self.get_context(), function, param_type, &format!("{}.addr", param.get_name()));
+            create_store(self.get_builder(), param_value, slot);
+            self.add_named_value(&param.get_name(), slot, param_type, &param.get_data_type());
+        }
+    
+        self.generate_block_ir(body, symbol_table_stack);
+        self.pop_scope();
+
+        // a void function may fall off the end of its body. Semantic analysis guarantees every other function
+        //     returns on each path, so a block left open there cannot be reached
+        if !has_terminator(self.get_current_block()) {
+            match return_type {
+                None => { void_return(self.get_builder()); },
+                Some(_) => { create_unreachable(self.get_builder()); },
+            }
+        }
+    
+        function
+    }
+    
+    /// Declares a function's prototype in the module so it can be called before its body is generated.
+    ///     If the function has already been declared, the existing declaration is returned
+    pub fn declare_fn_prototype(&mut self, name: &str, parameters: &[FunctionParameter], return_type: &Option<DataType>) -> LLVMValueRef {
+        if let Some(function) = get_named_function(self.get_module(), name) {
+            return function;
+        }
+
         let llvm_return_type = match return_type {
             Some(data_type) => self.map_data_type(data_type),
             None => void_type(self.get_context()),
@@ -39,28 +82,44 @@ impl IRGenerator {
         }
     
         let function_type = create_function_type(llvm_return_type, &llvm_param_types, false);
-        let function = add_function_to_module(self.get_module(), name, function_type);
-        let entry_bb = create_basic_block(self.get_context(), function, "entry");
-        position_builder(self.get_builder(), entry_bb);
-    
-        self.set_current_function(function);
-    
-        for node in body.iter() {
-            self.ir_router(node, symbol_table_stack);
-        }
-    
-        function
+        add_function_to_module(self.get_module(), name, function_type)
     }
     
-    /// TODO
-    pub fn generate_enum_declaration_ir(&mut self, _name: &String, _variants: &Vec<String>) -> LLVMValueRef {
+    /// Generates LLVM IR for an enum declaration. Enums are lowered to integer tags, numbered in declaration order,
+    ///     so there is nothing to emit beyond recording the variants
+    pub fn generate_enum_declaration_ir(&mut self, name: &str, variants: &[String]) -> LLVMValueRef {
+        self.add_enum(name, variants);
         std::ptr::null_mut()
-
     }
 
-    /// TODO
-    pub fn generate_struct_declaration_ir(&mut self, _name: &String, _fields: &Vec<(String, DataType)>) -> LLVMValueRef {
+    /// Generates LLVM IR for a struct declaration. The named type is created before any function is generated, so this
+    ///     only fills in its body if that has not happened yet
+    pub fn generate_struct_declaration_ir(&mut self, name: &str, fields: &[(String, DataType)]) -> LLVMValueRef {
+        self.define_struct_body(name, fields);
         std::ptr::null_mut()
-   
+    }
+
+    /// Creates the opaque named type of a struct and records its fields. If the struct has already been declared, the 
+    ///     existing type is returned
+    pub fn declare_struct_type(&mut self, name: &str, fields: &[(String, DataType)]) -> LLVMTypeRef {
+        if let Some((struct_type, _)) = self.get_struct(name) {
+            return *struct_type;
+        }
+
+        let struct_type: LLVMTypeRef = named_struct_type(self.get_context(), name);
+        let field_names: Vec<String> = fields.iter().map(|(field, _)| field.clone()).collect();
+        self.add_struct(name, struct_type, &field_names);
+        struct_type
+    }
+
+    /// Sets the body of a struct's named type from the types of its fields, declaring the type first if needed
+    pub fn define_struct_body(&mut self, name: &str, fields: &[(String, DataType)]) {
+        let struct_type: LLVMTypeRef = self.declare_struct_type(name, fields);
+        if !is_opaque_struct(struct_type) {
+            return;
+        }
+
+        let field_types: Vec<LLVMTypeRef> = fields.iter().map(|(_, data_type)| self.map_data_type(data_type)).collect();
+        set_struct_body(struct_type, &field_types, false);
     }
 }
\ No newline at end of file
@@ -1,12 +1,17 @@
 
+use std::sync::{Arc, Mutex};
+
 use llvm::prelude::LLVMTypeRef;
 
 use crate::{
     backend::{
         codegen::ir::ir_codegen_core::IRGenerator, 
-        llvm_lib::ir_lib::types::{boolean_type, int_type, float_type}
+        llvm_lib::ir_lib::types::{boolean_type, void_type, int_type, sized_int_type, float_type, enum_type, char_type, string_type}
     }, 
-    frontend::ast::data_type::DataType
+    frontend::{
+        ast::{ast_struct::ASTNode, data_type::DataType, syntax_element::SyntaxElement},
+        symbol_table::symbol_table_struct::{SymbolTableStack, SymbolValue},
+    },
 };
 
 impl IRGenerator {
@@ -15,8 +20,55 @@ impl IRGenerator {
         match data_type {
             DataType::Boolean => boolean_type(self.get_context()),
             DataType::Integer => int_type(self.get_context()),
+            sized if sized.is_integer() => sized_int_type(self.get_context(), sized.integer_width().unwrap_or(64)),
             DataType::Float => float_type(self.get_context()),
-            _ => unimplemented!("Unimplemented ir data type")
+            DataType::Char => char_type(self.get_context()),
+            DataType::String => string_type(self.get_context()),
+            DataType::Named(name) if self.get_enum_variants(name).is_some() => enum_type(self.get_context()),
+            DataType::Named(name) => match self.get_struct(name) {
+                Some((struct_type, _)) => *struct_type,
+                None => panic!("Type not found: {}", name),
+            },
+            DataType::None => void_type(self.get_context()),
+            // semantic analysis resolves or rejects these before code generation
+            DataType::Unknown | DataType::Function | DataType::Struct | DataType::Enum => panic!("{} has no IR type", data_type),
+            _ => unreachable!("integer types are mapped above"),
+        }
+    }
+
+    /// Finds the data type of an expression, or Unknown if it can't be told. LLVM integer types don't say whether 
+    ///     they are signed, so this decides between signed and unsigned instructions
+    pub fn expression_type(&self, node: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> DataType {
+        match node.get_element() {
+            SyntaxElement::Literal { data_type, .. } => data_type,
+            SyntaxElement::Variable { data_type, name } => self.get_named_value_type(&name).unwrap_or(data_type),
+            SyntaxElement::BinaryExpression { operator, .. } 
+                if matches!(operator.as_str(), "<" | ">" | "<=" | ">=" | "==" | "!=" | "&&" | "||") => DataType::Boolean,
+            SyntaxElement::BinaryExpression { left, .. } => self.expression_type(&left, symbol_table_stack),
+            SyntaxElement::UnaryExpression { operand, .. } => self.expression_type(&operand, symbol_table_stack),
+            SyntaxElement::FunctionCall { name, .. } => {
+                let symbol_value: Option<SymbolValue> = symbol_table_stack.lock().unwrap().lookup(&name).map(|info| info.get_value());
+                match symbol_value {
+                    Some(SymbolValue::FunctionValue { return_type: Some(return_type), .. }) => return_type,
+                    _ => DataType::Unknown,
+                }
+            },
+            SyntaxElement::FieldAccess { base, field } => {
+                let struct_name: String = match self.expression_type(&base, symbol_table_stack) {
+                    DataType::Named(struct_name) => struct_name,
+                    _ => return DataType::Unknown,
+                };
+                let symbol_value: Option<SymbolValue> = symbol_table_stack.lock().unwrap().lookup(&struct_name).map(|info| info.get_value());
+                match symbol_value {
+                    Some(SymbolValue::StructValue { fields }) => fields.into_iter()
+                        .find(|(name, _)| *name == field)
+                        .map(|(_, data_type)| data_type)
+                        .unwrap_or(DataType::Unknown),
+                    _ => DataType::Unknown,
+                }
+            },
+            SyntaxElement::StructLiteral { name, .. } => DataType::Named(name),
+            _ => DataType::Unknown,
         }
     }
-}
\ No newline at end of file
+}
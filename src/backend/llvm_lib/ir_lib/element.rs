@@ -17,6 +17,21 @@ pub fn create_integer(val: i64, context: LLVMContextRef) -> LLVMValueRef {
     }
 }
 
+/// creates an integer of the given width. the value's bits are used as they are, so unsigned values above the 
+///     signed range are kept
+pub(crate) fn create_sized_integer(val: u64, bits: u32, context: LLVMContextRef) -> LLVMValueRef {
+    unsafe {
+        core::LLVMConstInt(core::LLVMIntTypeInContext(context, bits), val, 0)
+    }
+}
+
+/// creates an enum tag
+pub(crate) fn create_enum_tag(index: usize, context: LLVMContextRef) -> LLVMValueRef {
+    unsafe {
+        core::LLVMConstInt(core::LLVMInt32TypeInContext(context), index as u64, 0)
+    }
+}
+
 /// creates a float
 pub fn create_float(val: f64, context: LLVMContextRef) -> LLVMValueRef {
     unsafe {
@@ -70,6 +85,37 @@ pub fn create_struct(values: &[LLVMValueRef], context: LLVMContextRef, packed: b
     }
 }
 
+/// creates an undefined value of a type, to build aggregates from
+pub(crate) fn create_undef(ty: LLVMTypeRef) -> LLVMValueRef {
+    unsafe {
+        core::LLVMGetUndef(ty)
+    }
+}
+
+/// creates an insertion of a value into a field of an aggregate
+pub(crate) fn create_insert_value(builder: LLVMBuilderRef, aggregate: LLVMValueRef, value: LLVMValueRef, index: u32, name: &str) -> LLVMValueRef {
+    let c_name = CString::new(name).expect("Failed to create CString for insertvalue");
+    unsafe {
+        core::LLVMBuildInsertValue(builder, aggregate, value, index, c_name.as_ptr())
+    }
+}
+
+/// creates a read of a field of an aggregate value
+pub(crate) fn create_extract_value(builder: LLVMBuilderRef, aggregate: LLVMValueRef, index: u32, name: &str) -> LLVMValueRef {
+    let c_name = CString::new(name).expect("Failed to create CString for extractvalue");
+    unsafe {
+        core::LLVMBuildExtractValue(builder, aggregate, index, c_name.as_ptr())
+    }
+}
+
+/// creates a pointer to a field of a struct behind a pointer
+pub(crate) fn create_struct_gep(builder: LLVMBuilderRef, struct_type: LLVMTypeRef, pointer: LLVMValueRef, index: u32, name: &str) -> LLVMValueRef {
+    let c_name = CString::new(name).expect("Failed to create CString for getelementptr");
+    unsafe {
+        core::LLVMBuildStructGEP2(builder, struct_type, pointer, index, c_name.as_ptr())
+    }
+}
+
 /// creates a global variable
 pub fn create_global_variable(module: LLVMModuleRef, initializer: LLVMValueRef, name: &str) -> LLVMValueRef {
     let c_name = CString::new(name).expect("Failed to create global variable name");
@@ -80,12 +126,29 @@ pub fn create_global_variable(module: LLVMModuleRef, initializer: LLVMValueRef,
     }
 }
 
-/// creates immutable (global) string
-pub fn create_string(val: &str, builder: LLVMBuilderRef) -> LLVMValueRef {
-    let c_val = CString::new(val).expect("Failed to create string");
+/// creates immutable (global) string as a private constant, returning a pointer to its first character.
+///     the bytes are passed with their length so strings may contain '\0', and a terminating '\0' is appended
+pub(crate) fn create_string(val: &str, context: LLVMContextRef, module: LLVMModuleRef, builder: LLVMBuilderRef) -> LLVMValueRef {
     let c_str_name = CString::new("const_str").expect("Failed to create string name");
     unsafe {
-        core::LLVMBuildGlobalStringPtr(builder, c_val.as_ptr(), c_str_name.as_ptr())
+        let initializer: LLVMValueRef = core::LLVMConstStringInContext(context, val.as_ptr() as *const _, val.len() as u32, 0);
+        let str_type: LLVMTypeRef = core::LLVMTypeOf(initializer);
+        let global_str: LLVMValueRef = core::LLVMAddGlobal(module, str_type, c_str_name.as_ptr());
+        core::LLVMSetInitializer(global_str, initializer);
+        core::LLVMSetLinkage(global_str, llvm::LLVMLinkage::LLVMPrivateLinkage);
+        core::LLVMSetGlobalConstant(global_str, 1);
+        core::LLVMSetUnnamedAddress(global_str, llvm::LLVMUnnamedAddr::LLVMGlobalUnnamedAddr);
+
+        let zero: LLVMValueRef = core::LLVMConstInt(core::LLVMInt32TypeInContext(context), 0, 0);
+        let mut indices: [LLVMValueRef; 2] = [zero, zero];
+        core::LLVMBuildInBoundsGEP2(builder, str_type, global_str, indices.as_mut_ptr(), indices.len() as u32, c_str_name.as_ptr())
+    }
+}
+
+/// creates a character, stored as its unicode scalar value
+pub(crate) fn create_char(val: char, context: LLVMContextRef) -> LLVMValueRef {
+    unsafe {
+        core::LLVMConstInt(core::LLVMInt32TypeInContext(context), val as u64, 0)
     }
 }
 
@@ -149,6 +212,66 @@ pub fn add_function_to_module(module: LLVMModuleRef, function_name: &str, functi
     }
 }
 
+/// retrieves a function from a module by name, if it has been added
+pub(crate) fn get_named_function(module: LLVMModuleRef, function_name: &str) -> Option<LLVMValueRef> {
+    let c_name = CString::new(function_name).expect("Failed to create CString for function name");
+    let function: LLVMValueRef = unsafe {
+        core::LLVMGetNamedFunction(module, c_name.as_ptr())
+    };
+    if function.is_null() {
+        None
+    } else {
+        Some(function)
+    }
+}
+
+/// creates a function call
+pub(crate) fn create_call(builder: LLVMBuilderRef, function: LLVMValueRef, arguments: &mut [LLVMValueRef], name: CString) -> LLVMValueRef {
+    unsafe {
+        core::LLVMBuildCall2(
+            builder,
+            core::LLVMGlobalGetValueType(function),
+            function,
+            arguments.as_mut_ptr(),
+            arguments.len() as u32,
+            name.as_ptr(),
+        )
+    }
+}
+
+/// creates a stack slot at the start of a function's entry block, so mem2reg can promote it
+pub(crate) fn create_entry_alloca(context: LLVMContextRef, function: LLVMValueRef, ty: LLVMTypeRef, name: &str) -> LLVMValueRef {
+    let c_name = CString::new(name).expect("Failed to create CString for alloca");
+    unsafe {
+        let builder: LLVMBuilderRef = core::LLVMCreateBuilderInContext(context);
+        let entry_bb: LLVMBasicBlockRef = core::LLVMGetEntryBasicBlock(function);
+        let first_instruction: LLVMValueRef = core::LLVMGetFirstInstruction(entry_bb);
+        if first_instruction.is_null() {
+            core::LLVMPositionBuilderAtEnd(builder, entry_bb);
+        } else {
+            core::LLVMPositionBuilderBefore(builder, first_instruction);
+        }
+        let alloca: LLVMValueRef = core::LLVMBuildAlloca(builder, ty, c_name.as_ptr());
+        core::LLVMDisposeBuilder(builder);
+        alloca
+    }
+}
+
+/// creates a store of a value into a pointer
+pub(crate) fn create_store(builder: LLVMBuilderRef, value: LLVMValueRef, pointer: LLVMValueRef) -> LLVMValueRef {
+    unsafe {
+        core::LLVMBuildStore(builder, value, pointer)
+    }
+}
+
+/// creates a load of a value from a pointer
+pub(crate) fn create_load(builder: LLVMBuilderRef, ty: LLVMTypeRef, pointer: LLVMValueRef, name: &str) -> LLVMValueRef {
+    let c_name = CString::new(name).expect("Failed to create CString for load");
+    unsafe {
+        core::LLVMBuildLoad2(builder, ty, pointer, c_name.as_ptr())
+    }
+}
+
 /// creates a conditional
 pub fn create_cond_br(builder: LLVMBuilderRef, condition: LLVMValueRef, then_bb: LLVMBasicBlockRef, else_bb: LLVMBasicBlockRef) -> LLVMValueRef {
     unsafe {
@@ -161,4 +284,24 @@ pub fn create_br(builder: LLVMBuilderRef, target_bb: LLVMBasicBlockRef) -> LLVMV
     unsafe {
         core::LLVMBuildBr(builder, target_bb)
     }
-}
\ No newline at end of file
+}
+/// creates a switch over an integer value, jumping to the default block when no case matches
+pub(crate) fn create_switch(builder: LLVMBuilderRef, value: LLVMValueRef, default_bb: LLVMBasicBlockRef, num_cases: u32) -> LLVMValueRef {
+    unsafe {
+        core::LLVMBuildSwitch(builder, value, default_bb, num_cases)
+    }
+}
+
+/// adds a case to a switch
+pub(crate) fn add_switch_case(switch: LLVMValueRef, value: LLVMValueRef, target_bb: LLVMBasicBlockRef) {
+    unsafe {
+        core::LLVMAddCase(switch, value, target_bb)
+    }
+}
+
+/// creates an unreachable instruction
+pub(crate) fn create_unreachable(builder: LLVMBuilderRef) -> LLVMValueRef {
+    unsafe {
+        core::LLVMBuildUnreachable(builder)
+    }
+}
@@ -27,19 +27,27 @@ pub fn build_mul(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue,
     }
 }
 
-/// basic division
-pub fn build_div(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
-        -> *mut llvm::LLVMValue {
+/// basic division, signed or unsigned
+pub(crate) fn build_div(builder: *mut llvm::LLVMBuilder, signed: bool, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, 
+        name: CString) -> *mut llvm::LLVMValue {
     unsafe {
-        core::LLVMBuildSDiv(builder, param_a, param_b, name.as_ptr()) // Signed division
+        if signed {
+            core::LLVMBuildSDiv(builder, param_a, param_b, name.as_ptr())
+        } else {
+            core::LLVMBuildUDiv(builder, param_a, param_b, name.as_ptr())
+        }
     }
 }
 
-/// modular arithmetic
-pub fn build_rem(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
-        -> *mut llvm::LLVMValue {
+/// modular arithmetic, signed or unsigned
+pub(crate) fn build_rem(builder: *mut llvm::LLVMBuilder, signed: bool, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, 
+        name: CString) -> *mut llvm::LLVMValue {
     unsafe {
-        core::LLVMBuildSRem(builder, param_a, param_b, name.as_ptr()) // Signed remainder
+        if signed {
+            core::LLVMBuildSRem(builder, param_a, param_b, name.as_ptr())
+        } else {
+            core::LLVMBuildURem(builder, param_a, param_b, name.as_ptr())
+        }
     }
 }
 
@@ -75,27 +83,33 @@ pub fn build_shl(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue,
     }
 }
 
-/// logical right shift
-pub fn build_shr(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
-        -> *mut llvm::LLVMValue {
+/// right shift, arithmetic (keeping the sign bit) when signed and logical otherwise
+pub(crate) fn build_shr(builder: *mut llvm::LLVMBuilder, signed: bool, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, 
+        name: CString) -> *mut llvm::LLVMValue {
     unsafe {
-        core::LLVMBuildLShr(builder, param_a, param_b, name.as_ptr())
+        if signed {
+            core::LLVMBuildAShr(builder, param_a, param_b, name.as_ptr())
+        } else {
+            core::LLVMBuildLShr(builder, param_a, param_b, name.as_ptr())
+        }
     }
 }
 
-/// greater than
-pub fn build_icmp_gt(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
-        -> *mut llvm::LLVMValue {
+/// greater than, signed or unsigned
+pub(crate) fn build_icmp_gt(builder: *mut llvm::LLVMBuilder, signed: bool, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, 
+        name: CString) -> *mut llvm::LLVMValue {
+    let predicate: llvm::LLVMIntPredicate = if signed { llvm::LLVMIntPredicate::LLVMIntSGT } else { llvm::LLVMIntPredicate::LLVMIntUGT };
     unsafe {
-        core::LLVMBuildICmp(builder, llvm::LLVMIntPredicate::LLVMIntSGT, param_a, param_b, name.as_ptr())
+        core::LLVMBuildICmp(builder, predicate, param_a, param_b, name.as_ptr())
     }
 }
 
-/// less than
-pub fn build_icmp_lt(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
-        -> *mut llvm::LLVMValue {
+/// less than, signed or unsigned
+pub(crate) fn build_icmp_lt(builder: *mut llvm::LLVMBuilder, signed: bool, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, 
+        name: CString) -> *mut llvm::LLVMValue {
+    let predicate: llvm::LLVMIntPredicate = if signed { llvm::LLVMIntPredicate::LLVMIntSLT } else { llvm::LLVMIntPredicate::LLVMIntULT };
     unsafe {
-        core::LLVMBuildICmp(builder, llvm::LLVMIntPredicate::LLVMIntSLT, param_a, param_b, name.as_ptr())
+        core::LLVMBuildICmp(builder, predicate, param_a, param_b, name.as_ptr())
     }
 }
 
@@ -107,6 +121,87 @@ pub fn build_icmp_eq(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMVa
     }
 }
 
+/// not equal
+pub(crate) fn build_icmp_ne(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
+        -> *mut llvm::LLVMValue {
+    unsafe {
+        core::LLVMBuildICmp(builder, llvm::LLVMIntPredicate::LLVMIntNE, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// less than or equal to, signed or unsigned
+pub(crate) fn build_icmp_le(builder: *mut llvm::LLVMBuilder, signed: bool, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, 
+        name: CString) -> *mut llvm::LLVMValue {
+    let predicate: llvm::LLVMIntPredicate = if signed { llvm::LLVMIntPredicate::LLVMIntSLE } else { llvm::LLVMIntPredicate::LLVMIntULE };
+    unsafe {
+        core::LLVMBuildICmp(builder, predicate, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// greater than or equal to, signed or unsigned
+pub(crate) fn build_icmp_ge(builder: *mut llvm::LLVMBuilder, signed: bool, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, 
+        name: CString) -> *mut llvm::LLVMValue {
+    let predicate: llvm::LLVMIntPredicate = if signed { llvm::LLVMIntPredicate::LLVMIntSGE } else { llvm::LLVMIntPredicate::LLVMIntUGE };
+    unsafe {
+        core::LLVMBuildICmp(builder, predicate, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// floating point addition
+pub(crate) fn build_fadd(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
+        -> *mut llvm::LLVMValue {
+    unsafe {
+        core::LLVMBuildFAdd(builder, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// floating point subtraction
+pub(crate) fn build_fsub(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
+        -> *mut llvm::LLVMValue {
+    unsafe {
+        core::LLVMBuildFSub(builder, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// floating point multiplication
+pub(crate) fn build_fmul(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
+        -> *mut llvm::LLVMValue {
+    unsafe {
+        core::LLVMBuildFMul(builder, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// floating point division
+pub(crate) fn build_fdiv(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
+        -> *mut llvm::LLVMValue {
+    unsafe {
+        core::LLVMBuildFDiv(builder, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// floating point remainder
+pub(crate) fn build_frem(builder: *mut llvm::LLVMBuilder, param_a: *mut llvm::LLVMValue, param_b: *mut llvm::LLVMValue, name: CString) 
+        -> *mut llvm::LLVMValue {
+    unsafe {
+        core::LLVMBuildFRem(builder, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// floating point comparison. Ordered predicates are used, so comparisons with NaN are false
+pub(crate) fn build_fcmp(builder: *mut llvm::LLVMBuilder, predicate: llvm::LLVMRealPredicate, param_a: *mut llvm::LLVMValue, 
+        param_b: *mut llvm::LLVMValue, name: CString) -> *mut llvm::LLVMValue {
+    unsafe {
+        core::LLVMBuildFCmp(builder, predicate, param_a, param_b, name.as_ptr())
+    }
+}
+
+/// floating point negation
+pub(crate) fn build_fnegation(builder: *mut llvm::LLVMBuilder, operand_ir: LLVMValueRef, name: CString) -> LLVMValueRef {
+    unsafe {
+        core::LLVMBuildFNeg(builder, operand_ir, name.as_ptr())
+    }
+}
+
 /// negation
 pub fn build_negation(builder: *mut llvm::LLVMBuilder, operand_ir: LLVMValueRef, name: CString) -> LLVMValueRef {
     unsafe {
@@ -1,5 +1,7 @@
 extern crate llvm_sys as llvm;
 
+use std::ffi::{CStr, CString};
+
 use llvm::{core, prelude::LLVMTypeRef};
 
 /// void type
@@ -16,10 +18,45 @@ pub fn int_type(context: *mut llvm::LLVMContext) -> *mut llvm::LLVMType {
     }
 }
 
-/// float type
+/// sized integer type, for integers that aren't 64 bits wide
+pub(crate) fn sized_int_type(context: *mut llvm::LLVMContext, bits: u32) -> *mut llvm::LLVMType {
+    unsafe {
+        core::LLVMIntTypeInContext(context, bits)
+    }
+}
+
+/// enum tag type
+pub(crate) fn enum_type(context: *mut llvm::LLVMContext) -> *mut llvm::LLVMType {
+    unsafe {
+        core::LLVMIntTypeInContext(context, 32)
+    }
+}
+
+/// float type, a 64-bit double to match float literals
 pub fn float_type(context: *mut llvm::LLVMContext) -> *mut llvm::LLVMType {
     unsafe {
-        core::LLVMFloatTypeInContext(context) 
+        core::LLVMDoubleTypeInContext(context) 
+    }
+}
+
+/// checks whether a type is the float type
+pub(crate) fn is_float_type(ty: LLVMTypeRef) -> bool {
+    unsafe {
+        core::LLVMGetTypeKind(ty) == llvm::LLVMTypeKind::LLVMDoubleTypeKind
+    }
+}
+
+/// character type, a 32-bit unicode scalar value
+pub(crate) fn char_type(context: *mut llvm::LLVMContext) -> *mut llvm::LLVMType {
+    unsafe {
+        core::LLVMInt32TypeInContext(context)
+    }
+}
+
+/// string type, a pointer to the first byte of the string's data
+pub(crate) fn string_type(context: *mut llvm::LLVMContext) -> *mut llvm::LLVMType {
+    unsafe {
+        core::LLVMPointerType(core::LLVMInt8TypeInContext(context), 0)
     }
 }
 
@@ -51,3 +88,45 @@ pub fn struct_type(context: *mut llvm::LLVMContext, element_types: &[LLVMTypeRef
     }
 }
 
+/// named struct type, created opaque so that its body can refer to other named structs
+pub(crate) fn named_struct_type(context: *mut llvm::LLVMContext, name: &str) -> *mut llvm::LLVMType {
+    let c_name = CString::new(name).expect("Failed to create CString for struct name");
+    unsafe {
+        core::LLVMStructCreateNamed(context, c_name.as_ptr())
+    }
+}
+
+/// sets the fields of an opaque named struct type
+pub(crate) fn set_struct_body(struct_type: LLVMTypeRef, element_types: &[LLVMTypeRef], packed: bool) {
+    unsafe {
+        core::LLVMStructSetBody(struct_type, element_types.as_ptr() as *mut _, element_types.len() as u32, packed as i32)
+    }
+}
+
+/// checks whether a struct type has not had its body set yet
+pub(crate) fn is_opaque_struct(struct_type: LLVMTypeRef) -> bool {
+    unsafe {
+        core::LLVMIsOpaqueStruct(struct_type) != 0
+    }
+}
+
+/// type of a struct's field
+pub(crate) fn struct_field_type(struct_type: LLVMTypeRef, index: u32) -> *mut llvm::LLVMType {
+    unsafe {
+        core::LLVMStructGetTypeAtIndex(struct_type, index)
+    }
+}
+
+/// name of a named struct type, if the type is one
+pub(crate) fn struct_type_name(ty: LLVMTypeRef) -> Option<String> {
+    unsafe {
+        if core::LLVMGetTypeKind(ty) != llvm::LLVMTypeKind::LLVMStructTypeKind {
+            return None;
+        }
+        let name = core::LLVMGetStructName(ty);
+        if name.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+    }
+}
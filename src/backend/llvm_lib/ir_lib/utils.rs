@@ -11,6 +11,13 @@ pub fn get_param(function: *mut llvm::LLVMValue, index: u32) -> *mut llvm::LLVMV
     }
 }
 
+/// Sets the name of a value as it appears in the IR
+pub(crate) fn set_value_name(value: *mut llvm::LLVMValue, name: &str) {
+    unsafe {
+        core::LLVMSetValueName2(value, name.as_ptr() as *const _, name.len())
+    }
+}
+
 /// Writes an LLVM module to a file
 pub fn write_to_file(module: &LLVMModuleRef, file_name: &str) -> Result<(), String> {
     if module.is_null() {
@@ -50,4 +57,17 @@ pub fn position_builder(builder: *mut llvm::LLVMBuilder, bb: *mut llvm::LLVMBasi
     unsafe {
         core::LLVMPositionBuilderAtEnd(builder, bb)
     }
-}
\ No newline at end of file
+}
+/// Gets the type of a value
+pub(crate) fn type_of(value: *mut llvm::LLVMValue) -> *mut llvm::LLVMType {
+    unsafe {
+        core::LLVMTypeOf(value)
+    }
+}
+
+/// Checks whether a basic block already ends in a terminator instruction
+pub(crate) fn has_terminator(bb: *mut llvm::LLVMBasicBlock) -> bool {
+    unsafe {
+        !core::LLVMGetBasicBlockTerminator(bb).is_null()
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap, fs, path::Path, sync::{Arc, Mutex}
+    collections::HashMap, fs, path::{Path, PathBuf}, sync::{Arc, Mutex}
 };
 
 use crate::{
@@ -8,15 +8,15 @@ use crate::{
         execute::execution_engine::ExecutionEngine, 
         llvm_lib::ir_lib::utils::write_to_file,
     }, 
-    constants::DEFAULT_PRIORITY_MODELEMENT, 
+    constants::{CONFIG_FILE_NAME, DEFAULT_PRIORITY_MODELEMENT}, 
     frontend::{
+        const_fold::const_fold_core::ConstFold,
         ast::{
             ast_stitcher::ast_stitch,
             ast_struct::{ModAST, ModElement, AST,}, 
-            sem_rule::{RulesConfig, SemanticRule,}, 
-            syntax_element::SyntaxElement
+            sem_rule::RulesConfig, 
         }, 
-        lexer::{lexer_core::Lexer, token::Token}, 
+        lexer::{lexer_core::Lexer, token::SpannedToken}, 
         parser::parser_core::Parser, sem_analysis::sem_analysis_core::SemAnalysis, 
         symbol_table::symbol_table_struct::SymbolTableStack, 
         utils::{entry_points::entry_points, error::ErrorType,},
@@ -30,34 +30,62 @@ pub fn compile(file_path: &str, jit: bool, emit_ir: bool) -> Result<Vec<u8>, Vec
 
     let entry_points: Vec<usize> = entry_points(path);
     let content: String = fs::read_to_string(path).expect("no file");
+    let line_offsets: Vec<usize> = line_offsets(&content);
 
     let mut mod_elements: Vec<ModElement> = Vec::new();
+    let mut errors: Vec<ErrorType> = Vec::new();
 
     if entry_points.is_empty() {
         panic!("empty file");
     }
 
-    for window in entry_points.windows(2) {
-        let start: usize = window[0];
-        let end: usize = window[1];
+    for (index, &start_line) in entry_points.iter().enumerate() {
+        let start: usize = line_offsets[start_line];
+        let end: usize = match entry_points.get(index + 1) {
+            Some(&next_line) => line_offsets[next_line],
+            None => content.len(),
+        };
         let slice: &str = &content[start..end];
 
-        match generate_mod_element(slice.to_string()) {
-            Ok(ast_with_sym_table) => mod_elements.push(ast_with_sym_table),
-            Err(errors) => return Err(errors),
+        match generate_mod_element(slice, start_line + 1, start) {
+            Ok(mod_element) => mod_elements.push(mod_element),
+            Err(e) => errors.extend(e),
         }
     }
 
-    let last_start: usize = *entry_points.last().unwrap();
-    match generate_mod_element(content[last_start..].to_string()) {
-        Ok(mod_element) => mod_elements.push(mod_element),
-        Err(errors) => return Err(errors),
+    if !errors.is_empty() {
+        report_errors(file_path, &errors);
+        return Err(errors);
     }
 
-    let rules: RulesConfig = read_config();
+    let config_path: PathBuf = path.with_file_name(CONFIG_FILE_NAME);
+    let rules: RulesConfig = read_config(&config_path).inspect_err(|errors| {
+        report_errors(&config_path.to_string_lossy(), errors);
+    })?;
     let mod_ast: ModAST = ast_stitch(mod_elements);
 
-    ast_to_obj(mod_ast, rules, jit, emit_ir)
+    ast_to_obj(mod_ast, rules, file_path, jit, emit_ir).inspect_err(|errors| report_errors(file_path, errors))
+}
+
+/// Finds the byte offset at which each line of the content starts
+fn line_offsets(content: &str) -> Vec<usize> {
+    let mut offsets: Vec<usize> = vec![0];
+    offsets.extend(content.match_indices('\n').map(|(index, _)| index + 1));
+    offsets
+}
+
+/// Prints errors along with their locations in the file
+fn report_errors(file_path: &str, errors: &[ErrorType]) {
+    for error in errors {
+        eprintln!("{}:{}", file_path, error);
+    }
+}
+
+/// Prints warnings along with their locations in the file
+fn report_warnings(file_path: &str, warnings: &[ErrorType]) {
+    for warning in warnings {
+        eprintln!("{}:warning: {}", file_path, warning);
+    }
 }
 
 /// Ensures the passed in file exists
@@ -69,16 +97,18 @@ fn validate_file_path(path: &Path, file_path: &str) -> Result<(), Vec<ErrorType>
     Ok(())
 }
 
-/// Reads a configuration file 
-fn read_config() -> RulesConfig {
-    let rules: HashMap<SyntaxElement, Vec<SemanticRule>> = HashMap::new();
-    RulesConfig::new(rules)
+/// Reads a configuration file, using the default rules if there is none
+fn read_config(config_path: &Path) -> Result<RulesConfig, Vec<ErrorType>> {
+    match fs::read_to_string(config_path) {
+        Ok(config) => RulesConfig::from_config(&config),
+        Err(_) => Ok(RulesConfig::new(HashMap::new())),
+    }
 }
 
-/// Generates a mod element from an input program
-fn generate_mod_element(content: String) -> Result<ModElement, Vec<ErrorType>> {
-    let tokens: Vec<Token> = Lexer::lex(&content)?;
-    let ast: AST = Parser::parse(tokens)?;
+/// Generates a mod element from an input program that starts at a given line and byte offset of its file
+fn generate_mod_element(content: &str, first_line: usize, first_offset: usize) -> Result<ModElement, Vec<ErrorType>> {
+    let tokens: Vec<SpannedToken> = Lexer::lex_with_doc_comments(content, first_line, first_offset)?;
+    let ast: AST = Parser::parse_with_spans(tokens)?;
     match SymbolTableStack::gen_sym_table_stack(ast) {
         Ok((ast, symbol_table_stack)) => {
             let arc_mutex_sym_table_stack = Arc::new(Mutex::new(symbol_table_stack));
@@ -91,8 +121,10 @@ fn generate_mod_element(content: String) -> Result<ModElement, Vec<ErrorType>> {
 }
 
 /// Generates object code, JIT or static from a module
-fn ast_to_obj(content: ModAST, rules: RulesConfig, jit: bool, emit_ir: bool) -> Result<Vec<u8>, Vec<ErrorType>> {
-    let sem_analysis_result: Result<ModAST, Vec<ErrorType>> = SemAnalysis::sem_analysis(content, rules);
+fn ast_to_obj(content: ModAST, rules: RulesConfig, file_path: &str, jit: bool, emit_ir: bool) -> Result<Vec<u8>, Vec<ErrorType>> {
+    let (sem_analysis_result, warnings) = SemAnalysis::sem_analysis_with_warnings(content, rules);
+    report_warnings(file_path, &warnings);
+    let sem_analysis_result: Result<ModAST, Vec<ErrorType>> = sem_analysis_result.and_then(ConstFold::const_fold);
 
     match sem_analysis_result {
         Ok(processed_content) => {
@@ -120,11 +152,6 @@ fn ast_to_obj(content: ModAST, rules: RulesConfig, jit: bool, emit_ir: bool) ->
 
             Ok(Vec::new()) 
         },
-        Err(sem_analysis_errors) => {
-            for error in &sem_analysis_errors {
-                eprintln!("Syntax Error: {:?}", error);
-            }
-            Err(sem_analysis_errors)
-        }
+        Err(sem_analysis_errors) => Err(sem_analysis_errors),
     }
 }
\ No newline at end of file
@@ -1,2 +1,5 @@
 /// Default Priority of a ModElement
-pub const DEFAULT_PRIORITY_MODELEMENT: i32 = -1;
\ No newline at end of file
+pub const DEFAULT_PRIORITY_MODELEMENT: i32 = -1;
+
+/// Name of the rules configuration file, read from the directory of the compiled file
+pub const CONFIG_FILE_NAME: &str = ".uc_config";
\ No newline at end of file
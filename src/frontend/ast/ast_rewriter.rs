@@ -0,0 +1,142 @@
+/*
+Walks an AST, building a rewritten copy of it
+ */
+
+use std::sync::{Arc, Mutex};
+
+use crate::frontend::{
+    ast::{
+        ast_struct::ASTNode,
+        syntax_element::{MatchArm, SyntaxElement},
+    },
+    symbol_table::symbol_table_struct::SymbolTableStack,
+};
+
+/// Pass that builds a rewritten copy of an AST, given the symbol tables of the element it belongs to. Implementors 
+///     override `rewrite_node` or `rewrite_block` for the elements they change, and call `rewrite_children` to 
+///     rebuild everything else
+pub trait ASTRewriter {
+    /// Rewrites a node and everything beneath it
+    fn rewrite_node(&mut self, node: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> ASTNode {
+        self.rewrite_children(node, symbol_table_stack)
+    }
+
+    /// Rewrites the statements of a block, such as a function body or a loop body
+    fn rewrite_block(&mut self, nodes: &[ASTNode], symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Vec<ASTNode> {
+        nodes.iter().map(|node| self.rewrite_node(node, symbol_table_stack)).collect()
+    }
+
+    /// Rewrites a boxed node
+    fn rewrite_boxed(&mut self, node: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Box<ASTNode> {
+        Box::new(self.rewrite_node(node, symbol_table_stack))
+    }
+
+    /// Rebuilds a node from its rewritten children, keeping its span and doc comment
+    fn rewrite_children(&mut self, node: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> ASTNode {
+        let mut children: Vec<ASTNode> = Vec::new();
+
+        let element: SyntaxElement = match node.get_element() {
+            SyntaxElement::ModuleExpression | SyntaxElement::TopLevelExpression => {
+                children = node.get_children().iter().map(|child| self.rewrite_node(child, symbol_table_stack)).collect();
+                node.get_element()
+            },
+            SyntaxElement::FunctionDeclaration { .. } => {
+                children = self.rewrite_block(&node.get_children(), symbol_table_stack);
+                node.get_element()
+            },
+            SyntaxElement::BinaryExpression { left, operator, right } => {
+                SyntaxElement::BinaryExpression {
+                    left: self.rewrite_boxed(&left, symbol_table_stack),
+                    operator,
+                    right: self.rewrite_boxed(&right, symbol_table_stack),
+                }
+            },
+            SyntaxElement::UnaryExpression { operator, operand } => {
+                SyntaxElement::UnaryExpression { operator, operand: self.rewrite_boxed(&operand, symbol_table_stack) }
+            },
+            SyntaxElement::Initialization { variable, data_type, value } => {
+                SyntaxElement::Initialization { variable, data_type, value: self.rewrite_boxed(&value, symbol_table_stack) }
+            },
+            SyntaxElement::Assignment { variable, value } => {
+                SyntaxElement::Assignment { variable, value: self.rewrite_boxed(&value, symbol_table_stack) }
+            },
+            SyntaxElement::Return { value } => {
+                SyntaxElement::Return { value: self.rewrite_boxed(&value, symbol_table_stack) }
+            },
+            SyntaxElement::FunctionCall { name, arguments } => {
+                SyntaxElement::FunctionCall {
+                    name,
+                    arguments: arguments.iter().map(|argument| self.rewrite_node(argument, symbol_table_stack)).collect(),
+                }
+            },
+            SyntaxElement::IfStatement { condition, then_branch, else_branch } => {
+                SyntaxElement::IfStatement {
+                    condition: self.rewrite_boxed(&condition, symbol_table_stack),
+                    then_branch: Box::new(self.rewrite_block(&then_branch, symbol_table_stack)),
+                    else_branch: else_branch.map(|else_branch| Box::new(self.rewrite_block(&else_branch, symbol_table_stack))),
+                }
+            },
+            SyntaxElement::WhileLoop { condition, body } => {
+                SyntaxElement::WhileLoop {
+                    condition: self.rewrite_boxed(&condition, symbol_table_stack),
+                    body: Box::new(self.rewrite_block(&body, symbol_table_stack)),
+                }
+            },
+            SyntaxElement::DoWhileLoop { body, condition } => {
+                SyntaxElement::DoWhileLoop {
+                    body: Box::new(self.rewrite_block(&body, symbol_table_stack)),
+                    condition: self.rewrite_boxed(&condition, symbol_table_stack),
+                }
+            },
+            SyntaxElement::ForLoop { initializer, condition, increment, body } => {
+                SyntaxElement::ForLoop {
+                    initializer: initializer.map(|initializer| self.rewrite_boxed(&initializer, symbol_table_stack)),
+                    condition: self.rewrite_boxed(&condition, symbol_table_stack),
+                    increment: increment.map(|increment| self.rewrite_boxed(&increment, symbol_table_stack)),
+                    body: Box::new(self.rewrite_block(&body, symbol_table_stack)),
+                }
+            },
+            SyntaxElement::MatchStatement { to_match, arms } => {
+                SyntaxElement::MatchStatement {
+                    to_match: self.rewrite_boxed(&to_match, symbol_table_stack),
+                    arms: arms.iter().map(|arm| {
+                        let variant: ASTNode = self.rewrite_node(&arm.get_variant(), symbol_table_stack);
+                        MatchArm::new(variant, self.rewrite_block(&arm.get_action(), symbol_table_stack))
+                    }).collect(),
+                }
+            },
+            SyntaxElement::StructLiteral { name, fields } => {
+                SyntaxElement::StructLiteral {
+                    name,
+                    fields: fields.iter().map(|(field, value)| (field.clone(), self.rewrite_node(value, symbol_table_stack))).collect(),
+                }
+            },
+            SyntaxElement::FieldAccess { base, field } => {
+                SyntaxElement::FieldAccess { base: self.rewrite_boxed(&base, symbol_table_stack), field }
+            },
+            SyntaxElement::FieldAssignment { base, field, value } => {
+                SyntaxElement::FieldAssignment {
+                    base: self.rewrite_boxed(&base, symbol_table_stack),
+                    field,
+                    value: self.rewrite_boxed(&value, symbol_table_stack),
+                }
+            },
+            element @ (SyntaxElement::NoExpression
+            | SyntaxElement::Literal { .. }
+            | SyntaxElement::Variable { .. }
+            | SyntaxElement::EnumVariant { .. }
+            | SyntaxElement::Wildcard
+            | SyntaxElement::Break
+            | SyntaxElement::Continue
+            | SyntaxElement::StructDeclaration { .. }
+            | SyntaxElement::EnumDeclaration { .. }) => element,
+        };
+
+        let mut rewritten: ASTNode = ASTNode::with_span(element, node.get_span());
+        if let Some(doc) = node.get_doc() {
+            rewritten.set_doc(doc);
+        }
+        rewritten.add_children(children);
+        rewritten
+    }
+}
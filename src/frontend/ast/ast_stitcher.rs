@@ -1,14 +1,22 @@
-use crate::frontend::ast::{
+use std::collections::HashMap;
+
+use crate::frontend::{
+    ast::{
         ast_struct::{
             ASTNode, 
             ModAST,
             ModElement,
         },
+        data_type::DataType,
         syntax_element::SyntaxElement,
-    };
+    },
+    symbol_table::symbol_table_struct::SymbolInfo,
+};
 
 /// Pieces together mod elements into a cohesive module
 pub fn ast_stitch(input: Vec<ModElement>) -> ModAST {
+    link_top_level_symbols(&input);
+
     let mut mod_ast: ModAST = ModAST::new(); 
     for mut mod_element in input {
         let root = mod_element.get_ast().get_root();
@@ -19,6 +27,36 @@ pub fn ast_stitch(input: Vec<ModElement>) -> ModAST {
     mod_ast
 }
 
+/// Shares the functions, structs and enums declared in each mod element's global scope with every other element,
+///     so they can refer to each other regardless of declaration order
+fn link_top_level_symbols(input: &[ModElement]) {
+    let mut top_level_symbols: HashMap<String, SymbolInfo> = HashMap::new();
+    for mod_element in input {
+        let stack = mod_element.get_sym_table_stack();
+        let stack = stack.lock().unwrap();
+        if let Some(global_scope) = stack.get_elements().first() {
+            for (name, info) in global_scope.lock().unwrap().get_values() {
+                if matches!(info.get_data_type(), DataType::Function | DataType::Struct | DataType::Enum) {
+                    top_level_symbols.entry(name.clone()).or_insert_with(|| info.clone());
+                }
+            }
+        }
+    }
+
+    for mod_element in input {
+        let stack = mod_element.get_sym_table_stack();
+        let stack = stack.lock().unwrap();
+        if let Some(global_scope) = stack.get_elements().first() {
+            let mut global_scope = global_scope.lock().unwrap();
+            for (name, info) in &top_level_symbols {
+                if global_scope.get(name).is_none() {
+                    global_scope.add(name.clone(), info.clone());
+                }
+            }
+        }
+    }
+}
+
 fn get_ast_priority(ast_root: ASTNode) -> i32 {
     match ast_root.get_element() {
         SyntaxElement::ModuleExpression => {
@@ -29,4 +67,4 @@ fn get_ast_priority(ast_root: ASTNode) -> i32 {
         },
         _ => panic!("Not a valid root expression: {:?}", ast_root.get_element())
     }
-}
\ No newline at end of file
+}
@@ -8,8 +8,9 @@ use std::sync::{Arc, Mutex};
 
 use std::fmt;
 use crate::frontend::{
-    ast::syntax_element::SyntaxElement, 
-    symbol_table::symbol_table_struct::SymbolTableStack
+    ast::{data_type::DataType, syntax_element::{MatchArm, SyntaxElement}}, 
+    symbol_table::symbol_table_struct::SymbolTableStack,
+    utils::span::Span,
 };
 
 /// A Module TODO rename this to Module
@@ -64,6 +65,11 @@ impl ModElement {
         self.ast.clone()
     }
 
+    /// Replaces the ast of the mod element
+    pub fn set_ast(&mut self, ast: AST) {
+        self.ast = ast;
+    }
+
     /// Retrieves the symbol table stack of the mod element
     pub fn get_sym_table_stack(&self) -> Arc<Mutex<SymbolTableStack>> {
         Arc::clone(&self.sym_table_stack)
@@ -106,6 +112,8 @@ pub struct AST {
 pub struct ASTNode {
     element: SyntaxElement,
     children: Vec<ASTNode>,
+    span: Span,
+    doc: Option<String>,
 }
 
 impl AST {
@@ -129,6 +137,18 @@ impl ASTNode {
         ASTNode {
             element,
             children: Vec::new(),
+            span: Span::default(),
+            doc: None,
+        }
+    }
+
+    /// Creates a new ast node at a location in the source code
+    pub fn with_span(element: SyntaxElement, span: Span) -> Self {
+        ASTNode {
+            element,
+            children: Vec::new(),
+            span,
+            doc: None,
         }
     }
 
@@ -142,6 +162,26 @@ impl ASTNode {
         self.children.clone()
     }
 
+    /// Gets the location of the node in the source code
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+
+    /// Sets the location of the node in the source code
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+
+    /// Gets the doc comment written above the node, if it has one
+    pub fn get_doc(&self) -> Option<String> {
+        self.doc.clone()
+    }
+
+    /// Sets the doc comment written above the node
+    pub fn set_doc(&mut self, doc: String) {
+        self.doc = Some(doc);
+    }
+
     /// Adds a child node
     pub fn add_child(&mut self, to_add: ASTNode) {
         self.children.push(to_add);
@@ -159,6 +199,81 @@ impl ASTNode {
             _ => false,
         }
     }
+
+    /// Checks if the element names storage that can be assigned to: a variable, or a field reached from one. Fields
+    ///     of temporary values such as calls and struct literals aren't assignable
+    pub fn is_assignable(&self) -> bool {
+        match &self.element {
+            SyntaxElement::Variable { .. } => true,
+            SyntaxElement::FieldAccess { base, field: _ } => base.is_assignable(),
+            _ => false,
+        }
+    }
+
+    /// Checks if every path through the element ends in a return statement
+    pub fn always_returns(&self) -> bool {
+        match &self.element {
+            SyntaxElement::Return { value: _ } => true,
+            SyntaxElement::IfStatement { condition: _, then_branch, else_branch: Some(else_branch) } => {
+                then_branch.iter().any(ASTNode::always_returns) && else_branch.iter().any(ASTNode::always_returns)
+            },
+            // a break or continue can leave the body before it returns
+            SyntaxElement::DoWhileLoop { body, condition: _ } => {
+                !body.iter().any(ASTNode::exits_loop) && body.iter().any(ASTNode::always_returns)
+            },
+            // semantic analysis rejects enum matches that miss a variant, so only a match on other types needs a
+            //     wildcard or both booleans to cover every value
+            SyntaxElement::MatchStatement { to_match: _, arms } => {
+                let covers_all: bool = arms.iter().any(MatchArm::is_wildcard)
+                    || (!arms.is_empty() && arms.iter().all(|arm| matches!(arm.get_variant().get_element(), SyntaxElement::EnumVariant { .. })))
+                    || ["true", "false"].iter().all(|boolean| arms.iter().any(|arm| matches!(
+                        arm.get_variant().get_element(), 
+                        SyntaxElement::Literal { data_type: DataType::Boolean, value } if value == *boolean
+                    )));
+                covers_all && arms.iter().all(|arm| arm.get_action().iter().any(ASTNode::always_returns))
+            },
+            _ => false,
+        }
+    }
+
+    /// Checks if the element holds a break or continue that leaves the innermost loop around it. Those inside a
+    ///     nested loop leave that loop instead
+    pub fn exits_loop(&self) -> bool {
+        match &self.element {
+            SyntaxElement::Break | SyntaxElement::Continue => true,
+            SyntaxElement::IfStatement { condition: _, then_branch, else_branch } => {
+                then_branch.iter().chain(else_branch.iter().flat_map(|branch| branch.iter())).any(ASTNode::exits_loop)
+            },
+            SyntaxElement::MatchStatement { to_match: _, arms } => {
+                arms.iter().any(|arm| arm.get_action().iter().any(ASTNode::exits_loop))
+            },
+            _ => false,
+        }
+    }
+
+    /// Retrieves the expressions the element holds directly. Nested blocks, and the initializer and increment of a 
+    ///     for loop, are statements and aren't included
+    pub fn get_expressions(&self) -> Vec<ASTNode> {
+        match &self.element {
+            SyntaxElement::Initialization { value, .. }
+            | SyntaxElement::Assignment { value, .. }
+            | SyntaxElement::Return { value } => vec![*value.clone()],
+            SyntaxElement::BinaryExpression { left, right, .. } => vec![*left.clone(), *right.clone()],
+            SyntaxElement::UnaryExpression { operand, .. } => vec![*operand.clone()],
+            SyntaxElement::FunctionCall { arguments, .. } => arguments.clone(),
+            SyntaxElement::IfStatement { condition, .. }
+            | SyntaxElement::WhileLoop { condition, .. }
+            | SyntaxElement::DoWhileLoop { condition, .. }
+            | SyntaxElement::ForLoop { condition, .. } => vec![*condition.clone()],
+            SyntaxElement::MatchStatement { to_match, arms } => {
+                std::iter::once(*to_match.clone()).chain(arms.iter().map(MatchArm::get_variant)).collect()
+            },
+            SyntaxElement::StructLiteral { fields, .. } => fields.iter().map(|(_, value)| value.clone()).collect(),
+            SyntaxElement::FieldAccess { base, .. } => vec![*base.clone()],
+            SyntaxElement::FieldAssignment { base, value, .. } => vec![*base.clone(), *value.clone()],
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl fmt::Display for ASTNode {
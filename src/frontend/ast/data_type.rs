@@ -1,16 +1,32 @@
 use std::fmt;
 
 /// Acceptable data types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DataType {
-    /// Integer type
+    /// Integer type, a signed 64-bit integer
     Integer,
+    /// Signed 8-bit integer type
+    I8,
+    /// Signed 16-bit integer type
+    I16,
+    /// Signed 32-bit integer type
+    I32,
+    /// Unsigned 8-bit integer type
+    U8,
+    /// Unsigned 16-bit integer type
+    U16,
+    /// Unsigned 32-bit integer type
+    U32,
+    /// Unsigned 64-bit integer type
+    U64,
     /// Float type
     Float,
     /// Boolean type
     Boolean,
     /// String type
     String,
+    /// Character type
+    Char,
     /// Function type
     Function,
     /// Unknown type
@@ -21,6 +37,56 @@ pub enum DataType {
     Struct,
     /// Enum type
     Enum,
+    /// User defined type, a struct or enum referred to by name
+    Named(String),
+}
+
+impl DataType {
+    /// Checks whether the type is one of the integer types
+    pub fn is_integer(&self) -> bool {
+        self.integer_width().is_some()
+    }
+
+    /// Checks whether the type is an unsigned integer type
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, DataType::U8 | DataType::U16 | DataType::U32 | DataType::U64)
+    }
+
+    /// Retrieves the number of bits in an integer type
+    pub fn integer_width(&self) -> Option<u32> {
+        match self {
+            DataType::I8 | DataType::U8 => Some(8),
+            DataType::I16 | DataType::U16 => Some(16),
+            DataType::I32 | DataType::U32 => Some(32),
+            DataType::Integer | DataType::U64 => Some(64),
+            _ => None,
+        }
+    }
+
+    /// Retrieves the smallest and largest values of an integer type
+    pub fn integer_range(&self) -> Option<(i128, i128)> {
+        let bits: u32 = self.integer_width()?;
+        if self.is_unsigned() {
+            Some((0, (1i128 << bits) - 1))
+        } else {
+            Some((-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1))
+        }
+    }
+
+    /// Retrieves the integer type named by an integer literal suffix, such as `u8`. `i64` is the default Integer type
+    pub fn from_suffix(suffix: &str) -> Option<DataType> {
+        match suffix {
+            "i8" => Some(DataType::I8),
+            "i16" => Some(DataType::I16),
+            "i32" => Some(DataType::I32),
+            "i64" => Some(DataType::Integer),
+            "u8" => Some(DataType::U8),
+            "u16" => Some(DataType::U16),
+            "u32" => Some(DataType::U32),
+            "u64" => Some(DataType::U64),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for DataType {
@@ -29,6 +95,27 @@ impl fmt::Display for DataType {
             DataType::Integer => {
                 write!(f, "Integer")
             },
+            DataType::I8 => {
+                write!(f, "I8")
+            },
+            DataType::I16 => {
+                write!(f, "I16")
+            },
+            DataType::I32 => {
+                write!(f, "I32")
+            },
+            DataType::U8 => {
+                write!(f, "U8")
+            },
+            DataType::U16 => {
+                write!(f, "U16")
+            },
+            DataType::U32 => {
+                write!(f, "U32")
+            },
+            DataType::U64 => {
+                write!(f, "U64")
+            },
             DataType::Float => {
                 write!(f, "Float")
             },
@@ -38,6 +125,9 @@ impl fmt::Display for DataType {
             DataType::String => {
                 write!(f, "String")
             },
+            DataType::Char => {
+                write!(f, "Char")
+            },
             DataType::Unknown => {
                 write!(f, "Unknown")
             },
@@ -52,6 +142,9 @@ impl fmt::Display for DataType {
             },
             DataType::None => {
                 write!(f, "None")
+            },
+            DataType::Named(name) => {
+                write!(f, "{}", name)
             }
         }
     }
@@ -11,4 +11,7 @@ pub mod syntax_element;
 pub mod ast_stitcher;
 
 /// Defines semantic rules
-pub mod sem_rule;
\ No newline at end of file
+pub mod sem_rule;
+
+/// Rewrites AST's node by node
+pub mod ast_rewriter;
@@ -1,57 +1,304 @@
 use std::{collections::HashMap, sync::Arc};
 
 use crate::frontend::{
-    ast::syntax_element::SyntaxElement, 
-    symbol_table::symbol_table_struct::SymbolTable, 
-    utils::error::ErrorType
+    ast::{ast_struct::ASTNode, syntax_element::{ElementKind, SyntaxElement}},
+    symbol_table::symbol_table_struct::SymbolTableStack,
+    utils::{error::ErrorType, span::Span}
 };
 
+/// Name of the rule set holding the built-in rules
+pub const BUILT_IN_RULE_SET: &str = "built_in";
+
+/// How a violated rule is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Rule is not checked
+    Off,
+
+    /// Violations are reported but compilation continues
+    Warning,
+
+    /// Violations stop compilation
+    Error,
+}
+
+impl Severity {
+    /// Reads a severity from its name in a configuration file
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "off" => Some(Severity::Off),
+            "warning" => Some(Severity::Warning),
+            "error" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+}
+
 /// Semantic rule
 #[derive(Clone)]
 pub struct SemanticRule {
-    check: Arc<dyn Fn(&SyntaxElement, &mut SymbolTable) -> Option<ErrorType>>,
+    name: String,
+    severity: Severity,
+    check: Arc<dyn Fn(&ASTNode, &SymbolTableStack) -> Option<ErrorType>>,
+}
+
+/// Named group of rules, each checked on the elements of one kind
+#[derive(Clone)]
+pub struct RuleSet {
+    name: String,
+    rules: Vec<(ElementKind, SemanticRule)>,
+    nested: HashMap<String, Vec<String>>, // names of the rules in each set added to this one
 }
 
 /// Rules configuration
 pub struct RulesConfig {
-    rules: HashMap<SyntaxElement, Vec<SemanticRule>>
+    rules: HashMap<ElementKind, Vec<SemanticRule>>,
+    rule_sets: HashMap<String, Vec<String>>, // names of the rules in each set
 }
 
 impl RulesConfig {
-    /// New configuration
-    pub fn new(rules: HashMap<SyntaxElement, Vec<SemanticRule>>) -> Self {
-        Self {
-            rules
+    /// New configuration, with the built-in rules at their default severities
+    pub fn new(rules: HashMap<ElementKind, Vec<SemanticRule>>) -> Self {
+        let mut rules_config: RulesConfig = Self {
+            rules,
+            rule_sets: HashMap::new(),
+        };
+        rules_config.add_rule_set(built_in_rules());
+        rules_config
+    }
+
+    /// Creates a configuration with the built-in rules from the contents of a configuration file
+    pub fn from_config(config: &str) -> Result<Self, Vec<ErrorType>> {
+        let mut rules_config: RulesConfig = RulesConfig::new(HashMap::new());
+        rules_config.apply_config(config)?;
+        Ok(rules_config)
+    }
+
+    /// Applies the contents of a configuration file. Each line sets a rule or rule set to a severity, as
+    ///     `name = severity` where severity is `on`, `off`, `warning` or `error`; `#` starts a comment
+    pub fn apply_config(&mut self, config: &str) -> Result<(), Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = Vec::new();
+
+        for (index, line) in config.lines().enumerate() {
+            let line: &str = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let Some((name, value)) = line.split_once('=') else {
+                errors.push(ErrorType::InvalidConfig { line: index + 1, message: format!("expected `rule = severity`, found `{}`", line) });
+                continue;
+            };
+            let (name, value) = (name.trim(), value.trim());
+
+            let found: bool = match (value, Severity::from_name(value)) {
+                // enabling keeps the severity of rules that are already on
+                ("on", _) => self.update_rules(name, |rule| if rule.severity == Severity::Off { rule.severity = Severity::Warning }),
+                (_, Some(severity)) => self.set_severity(name, severity),
+                (_, None) => {
+                    errors.push(ErrorType::InvalidConfig { line: index + 1, message: format!("unknown severity {}", value) });
+                    continue;
+                },
+            };
+            if !found {
+                errors.push(ErrorType::InvalidConfig { line: index + 1, message: format!("unknown rule {}", name) });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
 
     /// Retrieve complete configuration of rules
-    pub fn get_rules_config(&self) -> &HashMap<SyntaxElement, Vec<SemanticRule>> {
+    pub fn get_rules_config(&self) -> &HashMap<ElementKind, Vec<SemanticRule>> {
         &self.rules
     }
 
-    /// Retrieve rules for syntaxelement
+    /// Retrieve the rules that are switched on for the kind of a syntaxelement
     pub fn get_rules(&self, element: &SyntaxElement) -> Vec<SemanticRule> {
-        match self.rules.get(element) {
-            Some(rules) => rules.to_vec(),
+        match self.rules.get(&element.get_kind()) {
+            Some(rules) => rules.iter().filter(|rule| rule.get_severity() != Severity::Off).cloned().collect(),
             None => Vec::new(),
         }
     }
+
+    /// Adds a rule checked on every element of a kind
+    pub fn add_rule(&mut self, kind: ElementKind, rule: SemanticRule) {
+        self.rules.entry(kind).or_default().push(rule);
+    }
+
+    /// Adds every rule of a rule set, which can then be configured by the name of the set
+    pub fn add_rule_set(&mut self, rule_set: RuleSet) {
+        let names: Vec<String> = rule_set.get_rule_names();
+        for (set_name, set_names) in rule_set.nested.into_iter().chain([(rule_set.name, names)]) {
+            let existing: &mut Vec<String> = self.rule_sets.entry(set_name).or_default();
+            for name in set_names {
+                if !existing.contains(&name) {
+                    existing.push(name);
+                }
+            }
+        }
+        for (kind, rule) in rule_set.rules {
+            self.rules.entry(kind).or_default().push(rule);
+        }
+    }
+
+    /// Sets the severity of every rule with a name, or of every rule in a rule set with that name, returning whether
+    ///     any rule was found
+    pub fn set_severity(&mut self, name: &str, severity: Severity) -> bool {
+        self.update_rules(name, |rule| rule.severity = severity)
+    }
+
+    /// Updates every rule with a name, or in a rule set with that name, returning whether any rule was found
+    fn update_rules(&mut self, name: &str, update: impl Fn(&mut SemanticRule)) -> bool {
+        let names: Vec<String> = match self.rule_sets.get(name) {
+            Some(names) => names.clone(),
+            None => vec![name.to_string()],
+        };
+
+        let mut found: bool = false;
+        for rule in self.rules.values_mut().flatten() {
+            if names.contains(&rule.name) {
+                update(rule);
+                found = true;
+            }
+        }
+        found
+    }
+}
+
+impl RuleSet {
+    /// New, empty rule set
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            rules: Vec::new(),
+            nested: HashMap::new(),
+        }
+    }
+
+    /// Retrieves the name of the rule set
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Retrieves the rules of the set along with the kind of element each is checked on
+    pub fn get_rules(&self) -> &[(ElementKind, SemanticRule)] {
+        &self.rules
+    }
+
+    /// Retrieves the names of the rules in the set, without repeats
+    pub fn get_rule_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for (_, rule) in &self.rules {
+            if !names.contains(&rule.name) {
+                names.push(rule.name.clone());
+            }
+        }
+        names
+    }
+
+    /// Adds a rule checked on every element of a kind
+    pub fn add_rule(&mut self, kind: ElementKind, rule: SemanticRule) {
+        self.rules.push((kind, rule));
+    }
+
+    /// Adds every rule of another rule set to this one. The other set can still be configured by its own name
+    pub fn add_rule_set(&mut self, rule_set: RuleSet) {
+        self.nested.insert(rule_set.get_name().to_string(), rule_set.get_rule_names());
+        self.nested.extend(rule_set.nested);
+        self.rules.extend(rule_set.rules);
+    }
 }
 
 impl SemanticRule {
-    /// New semantic rule
-    pub fn new<F>(check_fn: F) -> Self 
+    /// New semantic rule, with a name that configuration files can refer to
+    pub fn new<F>(name: &str, severity: Severity, check_fn: F) -> Self
     where
-        F: Fn(&SyntaxElement, &mut SymbolTable) -> Option<ErrorType> + 'static
+        F: Fn(&ASTNode, &SymbolTableStack) -> Option<ErrorType> + 'static
     {
         Self {
+            name: name.to_string(),
+            severity,
             check: Arc::new(check_fn),
         }
     }
 
-    /// Execute rule on element given symbol table
-    pub fn apply_rule(&self, element: &SyntaxElement, symbol_table: &mut SymbolTable) -> Option<ErrorType> {
-        (self.check)(element, symbol_table)
+    /// Retrieves the name of the rule
+    pub fn get_name(&self) -> &str {
+        &self.name
     }
-}
\ No newline at end of file
+
+    /// Retrieves how violations of the rule are reported
+    pub fn get_severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Execute rule on node given the symbol tables in scope
+    pub fn apply_rule(&self, node: &ASTNode, symbol_table_stack: &SymbolTableStack) -> Option<ErrorType> {
+        (self.check)(node, symbol_table_stack)
+    }
+}
+
+/// Creates the set of built-in rules
+fn built_in_rules() -> RuleSet {
+    let mut rule_set: RuleSet = RuleSet::new(BUILT_IN_RULE_SET);
+
+    let empty_block: SemanticRule = SemanticRule::new("empty_block", Severity::Warning, |node, _| {
+        let empty: bool = match node.get_element() {
+            SyntaxElement::IfStatement { then_branch, .. } => then_branch.is_empty(),
+            SyntaxElement::WhileLoop { body, .. }
+            | SyntaxElement::DoWhileLoop { body, .. }
+            | SyntaxElement::ForLoop { body, .. } => body.is_empty(),
+            _ => false,
+        };
+        empty.then(|| violation("empty_block", "block has no statements".to_string(), node.get_span()))
+    });
+    for kind in [ElementKind::IfStatement, ElementKind::WhileLoop, ElementKind::DoWhileLoop, ElementKind::ForLoop] {
+        rule_set.add_rule(kind, empty_block.clone());
+    }
+
+    rule_set.add_rule(ElementKind::IfStatement, SemanticRule::new("constant_condition", Severity::Warning, |node, _| {
+        match node.get_element() {
+            SyntaxElement::IfStatement { condition, .. } => match condition.get_element() {
+                SyntaxElement::Literal { value, .. } =>
+                    Some(violation("constant_condition", format!("condition is always {}", value), node.get_span())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }));
+
+    rule_set.add_rule(ElementKind::Assignment, SemanticRule::new("self_assignment", Severity::Warning, |node, _| {
+        match node.get_element() {
+            SyntaxElement::Assignment { variable, value } => match value.get_element() {
+                SyntaxElement::Variable { name, .. } if name == variable =>
+                    Some(violation("self_assignment", format!("{} is assigned to itself", variable), node.get_span())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }));
+
+    let snake_case_names: SemanticRule = SemanticRule::new("snake_case_names", Severity::Off, |node, _| {
+        let name: String = match node.get_element() {
+            SyntaxElement::FunctionDeclaration { name, .. } => name,
+            SyntaxElement::Initialization { variable, .. } => variable,
+            _ => return None,
+        };
+        let snake_case: bool = name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        (!snake_case).then(|| violation("snake_case_names", format!("{} is not in snake_case", name), node.get_span()))
+    });
+    rule_set.add_rule(ElementKind::FunctionDeclaration, snake_case_names.clone());
+    rule_set.add_rule(ElementKind::Initialization, snake_case_names);
+
+    rule_set
+}
+
+/// Creates a violation of a built-in rule
+fn violation(rule: &str, message: String, span: Span) -> ErrorType {
+    ErrorType::RuleViolation { rule: rule.to_string(), message, span }
+}
@@ -44,12 +44,12 @@ impl fmt::Display for FunctionParameter {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MatchArm {
     variant: ASTNode,
-    action: ASTNode,
+    action: Vec<ASTNode>,
 }
 
 impl MatchArm {
     /// Creates a new match arm
-    pub fn new(variant: ASTNode, action: ASTNode) -> Self {
+    pub fn new(variant: ASTNode, action: Vec<ASTNode>) -> Self {
         Self {
             variant,
             action
@@ -61,10 +61,15 @@ impl MatchArm {
         self.variant.clone()
     }
 
-    /// Retrieves the action based on the variant
-    pub fn get_action(&self) -> ASTNode {
+    /// Retrieves the statements run when the variant matches
+    pub fn get_action(&self) -> Vec<ASTNode> {
         self.action.clone()
     }
+
+    /// Checks if the arm matches any value
+    pub fn is_wildcard(&self) -> bool {
+        self.variant.get_element() == SyntaxElement::Wildcard
+    }
 }
 
 /// Syntax element, an aspect of ASTNode's that make up an AST
@@ -222,6 +227,159 @@ pub enum SyntaxElement {
     Return {
         /// Value of return
         value: Box<ASTNode>,
+    },
+
+    /// Variant of an enum, named by its path
+    EnumVariant {
+        /// Name of the enum
+        enum_name: String,
+        /// Name of the variant
+        variant: String,
+    },
+
+    /// Pattern of a match arm that matches any value
+    Wildcard,
+
+    /// Struct literal
+    StructLiteral {
+        /// Name of the struct
+        name: String,
+        /// Fields of the struct and their values
+        fields: Vec<(String, ASTNode)>,
+    },
+
+    /// Read of a struct field
+    FieldAccess {
+        /// Struct the field belongs to
+        base: Box<ASTNode>,
+        /// Name of the field
+        field: String,
+    },
+
+    /// Assignment of a struct field
+    FieldAssignment {
+        /// Struct the field belongs to
+        base: Box<ASTNode>,
+        /// Name of the field
+        field: String,
+        /// Value to assign
+        value: Box<ASTNode>,
+    },
+}
+
+/// Kind of a syntax element, without the data it carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    /// No expression
+    NoExpression,
+
+    /// Module expression
+    ModuleExpression,
+
+    /// Top level expression
+    TopLevelExpression,
+
+    /// Literal
+    Literal,
+
+    /// Variable
+    Variable,
+
+    /// Binary expression
+    BinaryExpression,
+
+    /// If statement
+    IfStatement,
+
+    /// Assignment
+    Assignment,
+
+    /// Initialization
+    Initialization,
+
+    /// Function declaration
+    FunctionDeclaration,
+
+    /// For loop
+    ForLoop,
+
+    /// While loop
+    WhileLoop,
+
+    /// Do while loop
+    DoWhileLoop,
+
+    /// Break
+    Break,
+
+    /// Continue
+    Continue,
+
+    /// Match statement
+    MatchStatement,
+
+    /// Function call
+    FunctionCall,
+
+    /// Struct declaration
+    StructDeclaration,
+
+    /// Enum declaration
+    EnumDeclaration,
+
+    /// Unary expression
+    UnaryExpression,
+
+    /// Return
+    Return,
+
+    /// Enum variant
+    EnumVariant,
+
+    /// Wildcard
+    Wildcard,
+
+    /// Struct literal
+    StructLiteral,
+
+    /// Field access
+    FieldAccess,
+
+    /// Field assignment
+    FieldAssignment,
+}
+
+impl SyntaxElement {
+    /// Retrieves the kind of the element
+    pub fn get_kind(&self) -> ElementKind {
+        match self {
+            SyntaxElement::NoExpression => ElementKind::NoExpression,
+            SyntaxElement::ModuleExpression => ElementKind::ModuleExpression,
+            SyntaxElement::TopLevelExpression => ElementKind::TopLevelExpression,
+            SyntaxElement::Literal { .. } => ElementKind::Literal,
+            SyntaxElement::Variable { .. } => ElementKind::Variable,
+            SyntaxElement::BinaryExpression { .. } => ElementKind::BinaryExpression,
+            SyntaxElement::IfStatement { .. } => ElementKind::IfStatement,
+            SyntaxElement::Assignment { .. } => ElementKind::Assignment,
+            SyntaxElement::Initialization { .. } => ElementKind::Initialization,
+            SyntaxElement::FunctionDeclaration { .. } => ElementKind::FunctionDeclaration,
+            SyntaxElement::ForLoop { .. } => ElementKind::ForLoop,
+            SyntaxElement::WhileLoop { .. } => ElementKind::WhileLoop,
+            SyntaxElement::DoWhileLoop { .. } => ElementKind::DoWhileLoop,
+            SyntaxElement::Break => ElementKind::Break,
+            SyntaxElement::Continue => ElementKind::Continue,
+            SyntaxElement::MatchStatement { .. } => ElementKind::MatchStatement,
+            SyntaxElement::FunctionCall { .. } => ElementKind::FunctionCall,
+            SyntaxElement::StructDeclaration { .. } => ElementKind::StructDeclaration,
+            SyntaxElement::EnumDeclaration { .. } => ElementKind::EnumDeclaration,
+            SyntaxElement::UnaryExpression { .. } => ElementKind::UnaryExpression,
+            SyntaxElement::Return { .. } => ElementKind::Return,
+            SyntaxElement::EnumVariant { .. } => ElementKind::EnumVariant,
+            SyntaxElement::Wildcard => ElementKind::Wildcard,
+            SyntaxElement::StructLiteral { .. } => ElementKind::StructLiteral,
+            SyntaxElement::FieldAccess { .. } => ElementKind::FieldAccess,
+            SyntaxElement::FieldAssignment { .. } => ElementKind::FieldAssignment,
+        }
     }
 }
 
@@ -317,6 +475,21 @@ impl fmt::Display for SyntaxElement {
                 write!(f, "UnaryExpression(operator: {}, operand: {})", operator, operand),
             SyntaxElement::Return { value} => 
                 write!(f, "Return(value: {}),", value),
+            SyntaxElement::EnumVariant { enum_name, variant } => 
+                write!(f, "EnumVariant({}::{})", enum_name, variant),
+            SyntaxElement::Wildcard => write!(f, "Wildcard"),
+            SyntaxElement::StructLiteral { name, fields } => {
+                write!(f, "StructLiteral({}, [", name)?;
+                for (i, (field, value)) in fields.iter().enumerate() {
+                    if i > 0 { write!(f, ", ")?; }
+                    write!(f, "{}: {}", field, value)?;
+                }
+                write!(f, "])")
+            },
+            SyntaxElement::FieldAccess { base, field } => 
+                write!(f, "FieldAccess({}, {})", base, field),
+            SyntaxElement::FieldAssignment { base, field, value } => 
+                write!(f, "FieldAssignment({}, {}, {})", base, field, value),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,115 @@
+/*
+Evaluates constant expressions in an AST that has passed semantic analysis
+ */
+
+use std::sync::{Arc, Mutex};
+
+use crate::frontend::{
+    ast::{
+        ast_rewriter::ASTRewriter,
+        ast_struct::{ASTNode, ModAST, ModElement, AST},
+        data_type::DataType,
+        syntax_element::SyntaxElement,
+    },
+    symbol_table::symbol_table_struct::SymbolTableStack,
+    utils::error::ErrorType,
+};
+
+/// Structure for the constant folding phase
+pub struct ConstFold {
+    errors: Vec<ErrorType>, // division by a constant zero and overflowing constants found so far
+}
+
+impl ConstFold {
+    fn new() -> Self {
+        Self {
+            errors: Vec::new(),
+        }
+    }
+
+    /// Records an error found while folding
+    pub fn add_error(&mut self, error: ErrorType) {
+        self.errors.push(error);
+    }
+
+    /// Folds the constant expressions of every element of a module, and removes branches whose conditions are 
+    ///     constant. Expects the module to have passed semantic analysis, so operands of an operator share a type
+    pub fn const_fold(input: ModAST) -> Result<ModAST, Vec<ErrorType>> {
+        let mut const_fold: ConstFold = ConstFold::new();
+        let mut input: ModAST = input;
+        let elements: Vec<ModElement> = input.get_children().clone().into_sorted_vec();
+        let mut folded: ModAST = ModAST::new();
+
+        for mut mod_element in elements {
+            let root: ASTNode = const_fold.rewrite_node(&mod_element.get_ast().get_root(), &mod_element.get_sym_table_stack());
+            mod_element.set_ast(AST::new(root));
+            folded.add_child(mod_element);
+        }
+
+        if const_fold.errors.is_empty() {
+            return Ok(folded);
+        }
+        Err(const_fold.errors)
+    }
+}
+
+impl ASTRewriter for ConstFold {
+    /// Folds the constant expressions of a node and everything beneath it
+    fn rewrite_node(&mut self, node: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> ASTNode {
+        let node: ASTNode = self.rewrite_children(node, symbol_table_stack);
+        let folded: Option<SyntaxElement> = match node.get_element() {
+            SyntaxElement::BinaryExpression { left, operator, right } => self.fold_binary(&left, &operator, &right, node.get_span()),
+            SyntaxElement::UnaryExpression { operator, operand } => self.fold_unary(&operator, &operand, node.get_span()),
+            _ => None,
+        };
+        match folded {
+            Some(literal) => ASTNode::with_span(literal, node.get_span()),
+            None => node,
+        }
+    }
+
+    /// Folds the statements of a block. An if statement with a constant condition is replaced by the branch it 
+    ///     takes, and a while loop whose condition is false is dropped
+    fn rewrite_block(&mut self, nodes: &[ASTNode], symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Vec<ASTNode> {
+        let mut folded: Vec<ASTNode> = Vec::new();
+
+        for node in nodes {
+            let node: ASTNode = self.rewrite_node(node, symbol_table_stack);
+            match node.get_element() {
+                SyntaxElement::IfStatement { condition, then_branch, else_branch } => match constant_condition(&condition) {
+                    Some(taken) => {
+                        let branch: Vec<ASTNode> = if taken { *then_branch } else { else_branch.map(|branch| *branch).unwrap_or_default() };
+                        // a branch that declares variables keeps its own scope, so its names can't clash with the block's
+                        if branch.iter().any(|statement| matches!(statement.get_element(), SyntaxElement::Initialization { .. })) {
+                            let condition: ASTNode = ASTNode::with_span(
+                                SyntaxElement::Literal { data_type: DataType::Boolean, value: "true".to_string() }, 
+                                condition.get_span(),
+                            );
+                            let element: SyntaxElement = SyntaxElement::IfStatement { 
+                                condition: Box::new(condition), 
+                                then_branch: Box::new(branch), 
+                                else_branch: None,
+                            };
+                            folded.push(ASTNode::with_span(element, node.get_span()));
+                        } else {
+                            folded.extend(branch);
+                        }
+                    },
+                    None => folded.push(node),
+                },
+                SyntaxElement::WhileLoop { condition, .. } if constant_condition(&condition) == Some(false) => {},
+                _ => folded.push(node),
+            }
+        }
+
+        folded
+    }
+}
+
+/// Retrieves the value of a condition that is a boolean literal
+fn constant_condition(condition: &ASTNode) -> Option<bool> {
+    match condition.get_element() {
+        SyntaxElement::Literal { data_type: DataType::Boolean, value } => value.parse::<bool>().ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,144 @@
+/*
+Evaluates operators applied to literals
+ */
+
+use std::cmp::Ordering;
+
+use crate::frontend::{
+    ast::{ast_struct::ASTNode, data_type::DataType, syntax_element::SyntaxElement},
+    const_fold::const_fold_core::ConstFold,
+    utils::{error::ErrorType, span::Span},
+};
+
+/// The value of a literal that can be computed with
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Constant {
+    Integer(i128),
+    Float(f64),
+    Boolean(bool),
+    Char(char),
+}
+
+impl ConstFold {
+    /// Evaluates a binary expression whose operands are both literals, giving the resulting literal. Integer division 
+    ///     or modulo by a literal zero and integer results outside of their type are reported, whatever the left operand
+    pub fn fold_binary(&mut self, left: &ASTNode, operator: &str, right: &ASTNode, span: Span) -> Option<SyntaxElement> {
+        let (data_type, right_value) = literal_constant(right)?;
+        if matches!(operator, "/" | "%") && right_value == Constant::Integer(0) {
+            self.add_error(ErrorType::DivisionByZero { operation: format!("{} {} 0", describe(left), operator), span });
+            return None;
+        }
+        let (_, left_value) = literal_constant(left)?;
+
+        let result: Constant = match (left_value, right_value) {
+            (Constant::Integer(a), Constant::Integer(b)) => match operator {
+                "+" => self.checked_integer(a.checked_add(b), &data_type, span)?,
+                "-" => self.checked_integer(a.checked_sub(b), &data_type, span)?,
+                "*" => self.checked_integer(a.checked_mul(b), &data_type, span)?,
+                "/" => Constant::Integer(a / b),
+                "%" => Constant::Integer(a % b),
+                // bitwise exclusive or, as ^ is lowered
+                "^" => Constant::Integer(a ^ b),
+                _ => Constant::Boolean(compare(operator, a.partial_cmp(&b))?),
+            },
+            (Constant::Float(a), Constant::Float(b)) => match operator {
+                "+" => Constant::Float(a + b),
+                "-" => Constant::Float(a - b),
+                "*" => Constant::Float(a * b),
+                "/" => Constant::Float(a / b),
+                "%" => Constant::Float(a % b),
+                _ => Constant::Boolean(compare(operator, a.partial_cmp(&b))?),
+            },
+            (Constant::Boolean(a), Constant::Boolean(b)) => match operator {
+                "&&" => Constant::Boolean(a && b),
+                "||" => Constant::Boolean(a || b),
+                _ => Constant::Boolean(compare(operator, a.partial_cmp(&b))?),
+            },
+            (Constant::Char(a), Constant::Char(b)) => Constant::Boolean(compare(operator, a.partial_cmp(&b))?),
+            _ => return None,
+        };
+
+        let data_type: DataType = if matches!(result, Constant::Boolean(_)) { DataType::Boolean } else { data_type };
+        self.literal_from(result, data_type, span)
+    }
+
+    /// Evaluates a unary expression whose operand is a literal, giving the resulting literal
+    pub fn fold_unary(&mut self, operator: &str, operand: &ASTNode, span: Span) -> Option<SyntaxElement> {
+        let (data_type, value) = literal_constant(operand)?;
+        let result: Constant = match (operator, value) {
+            ("-", Constant::Integer(a)) => Constant::Integer(-a),
+            ("-", Constant::Float(a)) => Constant::Float(-a),
+            ("!", Constant::Boolean(a)) => Constant::Boolean(!a),
+            _ => return None,
+        };
+        self.literal_from(result, data_type, span)
+    }
+
+    /// Gives the result of checked integer arithmetic, reporting an overflow when there is none
+    fn checked_integer(&mut self, value: Option<i128>, data_type: &DataType, span: Span) -> Option<Constant> {
+        match value {
+            Some(a) => Some(Constant::Integer(a)),
+            None => {
+                self.add_error(ErrorType::IntegerOverflow { data_type: data_type.to_string(), span });
+                None
+            },
+        }
+    }
+
+    /// Creates the literal for a computed value, reporting integers that don't fit their type
+    fn literal_from(&mut self, value: Constant, data_type: DataType, span: Span) -> Option<SyntaxElement> {
+        let value: String = match value {
+            Constant::Integer(a) => {
+                let (min, max) = data_type.integer_range()?;
+                if a < min || a > max {
+                    self.add_error(ErrorType::IntegerOverflow { data_type: data_type.to_string(), span });
+                    return None;
+                }
+                a.to_string()
+            },
+            Constant::Float(a) => format!("{:?}", a),
+            Constant::Boolean(a) => a.to_string(),
+            Constant::Char(a) => a.to_string(),
+        };
+        Some(SyntaxElement::Literal { data_type, value })
+    }
+}
+
+/// Retrieves the type and value of a literal node
+fn literal_constant(node: &ASTNode) -> Option<(DataType, Constant)> {
+    let (data_type, value) = match node.get_element() {
+        SyntaxElement::Literal { data_type, value } => (data_type, value),
+        _ => return None,
+    };
+    let constant: Constant = match &data_type {
+        integer if integer.is_integer() => Constant::Integer(value.parse::<i128>().ok()?),
+        DataType::Float => Constant::Float(value.parse::<f64>().ok()?),
+        DataType::Boolean => Constant::Boolean(value.parse::<bool>().ok()?),
+        DataType::Char => Constant::Char(value.parse::<char>().ok()?),
+        _ => return None,
+    };
+    Some((data_type, constant))
+}
+
+/// Applies a comparison operator to the ordering of two values. Comparisons with NaN are all false, matching the 
+///     ordered float comparisons that are generated for them
+fn compare(operator: &str, ordering: Option<Ordering>) -> Option<bool> {
+    match operator {
+        "==" => Some(ordering == Some(Ordering::Equal)),
+        "!=" => Some(matches!(ordering, Some(Ordering::Less | Ordering::Greater))),
+        "<" => Some(ordering == Some(Ordering::Less)),
+        ">" => Some(ordering == Some(Ordering::Greater)),
+        "<=" => Some(matches!(ordering, Some(Ordering::Less | Ordering::Equal))),
+        ">=" => Some(matches!(ordering, Some(Ordering::Greater | Ordering::Equal))),
+        _ => None,
+    }
+}
+
+/// Describes an operand for an error message
+fn describe(node: &ASTNode) -> String {
+    match node.get_element() {
+        SyntaxElement::Literal { value, .. } => value,
+        SyntaxElement::Variable { name, .. } => name,
+        _ => "expression".to_string(),
+    }
+}
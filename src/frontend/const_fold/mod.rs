@@ -0,0 +1,4 @@
+/// Core of constant folding
+pub mod const_fold_core;
+
+mod const_fold_expression;
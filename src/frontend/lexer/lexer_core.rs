@@ -3,39 +3,73 @@ Converts raw text into lexemes
 */
 
 use crate::frontend::{
-    lexer::token::Token,
-    utils::error::ErrorType,
+    ast::data_type::DataType,
+    lexer::token::{SpannedToken, Token},
+    utils::{error::ErrorType, span::Span},
 };
 
+/// Problems found while reading a token, before its location is known
+enum LexError {
+    Unrecognized(String),
+    Unterminated,
+    InvalidEscape(String),
+    InvalidLiteral(String),
+}
+
 /// Structure of the lexing process
 pub struct Lexer {
     input: Vec<char>, // Source code
     position: usize, // Current position in source code
     current: char, // Current character being read
+    offset: usize, // Byte offset of the current character in the source file
+    line: usize, // Line of the current character in the source file
+    column: usize, // Column of the current character in the source file
+    keep_doc_comments: bool, // Whether '///' comments become tokens instead of being skipped
 }
 
 impl Lexer {
     /// Creates a new lexer
-    fn new(input: Vec<char>) -> Self {
+    fn new(input: Vec<char>, first_line: usize, first_offset: usize, keep_doc_comments: bool) -> Self {
+        let current: char = input.first().copied().unwrap_or('~'); // EOF token if there is no input
         Self {
             input,
             position: 0,
-            current: '~', // EOF token, set initially but not necessarily the first token
+            current,
+            offset: first_offset,
+            line: first_line,
+            column: 1,
+            keep_doc_comments,
         }
     }
 
     /// Returns a vector of tokens
     pub fn lex(input: &str) -> Result<Vec<Token>, Vec<ErrorType>> {
-        let mut lexer: Lexer = Lexer::new(input.chars().collect());
+        let tokens: Vec<SpannedToken> = Lexer::lex_with_spans(input, 1, 0)?;
+        Ok(tokens.into_iter().map(|token| token.get_token()).collect())
+    }
+
+    /// Returns a vector of tokens along with their locations. `first_line` and `first_offset` are the
+    ///     line and byte offset at which the input starts in its source file
+    pub fn lex_with_spans(input: &str, first_line: usize, first_offset: usize) -> Result<Vec<SpannedToken>, Vec<ErrorType>> {
+        Lexer::new(input.chars().collect(), first_line, first_offset, false).tokenize()
+    }
+
+    /// Returns a vector of tokens along with their locations, like `lex_with_spans`, but keeps '///' doc comments 
+    ///     as tokens so the parser can attach them to declarations
+    pub fn lex_with_doc_comments(input: &str, first_line: usize, first_offset: usize) -> Result<Vec<SpannedToken>, Vec<ErrorType>> {
+        Lexer::new(input.chars().collect(), first_line, first_offset, true).tokenize()
+    }
+
+    /// Reads every token of the input, collecting the errors of any that can't be read
+    fn tokenize(mut self) -> Result<Vec<SpannedToken>, Vec<ErrorType>> {
         let mut errors: Vec<ErrorType> = Vec::new();
-        let mut tokens: Vec<Token> = Vec::new();
-        lexer.current = lexer.input[0];
+        let mut tokens: Vec<SpannedToken> = Vec::new();
 
         loop {
-            let token: Result<Token, ErrorType> = lexer.next_token();
+            let token: Result<SpannedToken, ErrorType> = self.next_token();
             match token {
                 Ok(token) => {
-                    if token == Token::EOF {
+                    if token.get_token() == Token::EOF {
                         tokens.push(token);
                         break;
                     }
@@ -55,6 +89,15 @@ impl Lexer {
     
     /// Advances the currently read character
     fn read_char(&mut self) {
+        if let Some(&previous) = self.input.get(self.position) {
+            self.offset += previous.len_utf8();
+            if previous == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
         self.position += 1;
         if self.position >= self.input.len() {
             self.current = '~';
@@ -63,28 +106,121 @@ impl Lexer {
         }
     }
 
-    /// Ignores whitespace
-    fn skip_whitespace(&mut self) {
+    /// Ignores whitespace and comments, other than doc comments that are being kept
+    fn skip_whitespace(&mut self) -> Result<(), ErrorType> {
         loop {
             if matches!(self.current, ' ' | '\t' | '\n' | '\r') {
                 self.read_char();
             }
+            else if self.current == '/' && self.peek_char() == '/' && !(self.keep_doc_comments && self.at_doc_comment()) {
+                while self.position < self.input.len() && self.current != '\n' {
+                    self.read_char();
+                }
+            }
+            else if self.current == '/' && self.peek_char() == '*' {
+                self.skip_block_comment()?;
+            }
             else {
-                break;
+                return Ok(());
             }
         }
     }
 
+    /// Skips a block comment starting at the current '/*', along with any block comments nested in it
+    fn skip_block_comment(&mut self) -> Result<(), ErrorType> {
+        let start: usize = self.offset;
+        let line: usize = self.line;
+        let column: usize = self.column;
+        let mut depth: usize = 0;
+
+        loop {
+            if self.position >= self.input.len() {
+                return Err(ErrorType::UnterminatedComment { span: Span::new(start, self.offset, line, column) });
+            }
+            if self.current == '/' && self.peek_char() == '*' {
+                self.read_char();
+                depth += 1;
+            }
+            else if self.current == '*' && self.peek_char() == '/' {
+                self.read_char();
+                depth -= 1;
+                if depth == 0 {
+                    self.read_char();
+                    return Ok(());
+                }
+            }
+            self.read_char();
+        }
+    }
+
+    /// Checks whether the current '//' starts a doc comment, which has exactly three slashes
+    fn at_doc_comment(&self) -> bool {
+        self.peek_nth_char(2) == '/' && self.peek_nth_char(3) != '/'
+    }
+
+    /// Reads the text of the doc comment starting at the current '///', up to the end of its line. 
+    ///     A single space after the slashes is dropped
+    fn read_doc_comment(&mut self) -> Vec<char> {
+        self.read_char();
+        self.read_char();
+        let mut text: Vec<char> = Vec::new();
+        while !matches!(self.peek_char(), '\n' | '\r') && self.position + 1 < self.input.len() {
+            self.read_char();
+            text.push(self.current);
+        }
+        if text.first() == Some(&' ') {
+            text.remove(0);
+        }
+        text
+    }
+
     /// Returns the current token type and advances to the next token
-    fn next_token(&mut self) -> Result<Token, ErrorType> {
-        self.skip_whitespace();
+    fn next_token(&mut self) -> Result<SpannedToken, ErrorType> {
+        self.skip_whitespace()?;
+
+        let start: usize = self.offset;
+        let line: usize = self.line;
+        let column: usize = self.column;
 
-        let tok: Result<Token, ErrorType> = match self.current {
+        let tok: Result<Token, LexError> = match self.current {
             '~' => Ok(Token::EOF),
 
-            '/' => Ok(Token::DIVIDE),
-            '-' => Ok(Token::MINUS),
-            '+' => Ok(Token::PLUS),
+            '"' => self.read_quoted('"').map(Token::STRING),
+            '\'' => self.read_quoted('\'').and_then(|characters| match characters[..] {
+                [character] => Ok(Token::CHAR(character)),
+                _ => Err(LexError::Unrecognized(format!("'{}'", characters.iter().collect::<String>()))),
+            }),
+
+            '/' => {
+                if self.peek_char() == '/' {
+                    Ok(Token::DOCCOMMENT(self.read_doc_comment()))
+                }
+                else if self.peek_char() == '=' {
+                    self.read_char();
+                    Ok(Token::DIVIDEASSIGN)
+                }
+                else {
+                    Ok(Token::DIVIDE)
+                }
+            },
+            '-' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    Ok(Token::MINUSASSIGN)
+                }
+                else {
+                    Ok(Token::MINUS)
+                }
+            },
+            '+' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    Ok(Token::PLUSASSIGN)
+                }
+                else {
+                    Ok(Token::PLUS)
+                }
+            },
             '=' => {
                 if self.peek_char() == '=' {
                     self.read_char();
@@ -114,7 +250,15 @@ impl Lexer {
                 }
             },
             ',' => Ok(Token::COMMA),
-            '%' => Ok(Token::MOD),
+            '%' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    Ok(Token::MODASSIGN)
+                }
+                else {
+                    Ok(Token::MOD)
+                }
+            },
             '[' => Ok(Token::LBRACE),
             ']' => Ok(Token::RBRACE),
             '.' => Ok(Token::DOT),
@@ -127,8 +271,16 @@ impl Lexer {
                     Ok(Token::LOGICALNOT)
                 }
             },
-            '*' => Ok(Token::MULTIPLY),
-            '^' => Ok(Token::EXPONENT),
+            '*' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    Ok(Token::MULTIPLYASSIGN)
+                }
+                else {
+                    Ok(Token::MULTIPLY)
+                }
+            },
+            '^' => Ok(Token::BITWISEXOR),
             
             '<' => {
                 if self.peek_char() == '=' {
@@ -149,73 +301,225 @@ impl Lexer {
 
             '&' => {
                 if self.peek_char() == '&' {
-                    self.read_char(); 
                     self.read_char(); 
                     Ok(Token::LOGICALAND)
                 } else {
-                    let mut err_token = String::new();
-                    err_token.push(self.current);
-                    Err(ErrorType::UnrecognizedToken { token: err_token })
+                    Err(LexError::Unrecognized(self.current.to_string()))
                 }
             },
             '|' => {
                 if self.peek_char() == '|' {
-                    self.read_char(); 
                     self.read_char(); 
                     Ok(Token::LOGICALOR)
                 } else {
-                    let mut err_token = String::new();
-                    err_token.push(self.current);
-                    Err(ErrorType::UnrecognizedToken { token: err_token })
+                    Err(LexError::Unrecognized(self.current.to_string()))
                 }
             },
             _ if is_letter(self.current) => {
                 let identifier: Vec<char> = self.read_identifier();
-                Ok(get_token(&identifier).unwrap_or_else(|_| Token::IDENTIFIER(identifier))) // i don't love this solution
+                Ok(get_token(&identifier).unwrap_or(Token::IDENTIFIER(identifier)))
             },
-            _ if is_digit(self.current) => Ok(Token::INT(self.read_number())),
+            _ if is_digit(self.current) => self.read_number(),
 
-            _ => { 
-                let mut err_token = String::new();
-                err_token.push(self.current);
-                Err(ErrorType::UnrecognizedToken { token: err_token })
-            },
+            _ => Err(LexError::Unrecognized(self.current.to_string())),
         };
 
         self.read_char();
-        tok
+        let span: Span = Span::new(start, self.offset, line, column);
+        match tok {
+            Ok(token) => Ok(SpannedToken::new(token, span)),
+            Err(LexError::Unrecognized(token)) => Err(ErrorType::UnrecognizedToken { token, span }),
+            Err(LexError::Unterminated) => Err(ErrorType::UnterminatedLiteral { span }),
+            Err(LexError::InvalidEscape(sequence)) => Err(ErrorType::InvalidEscape { sequence, span }),
+            Err(LexError::InvalidLiteral(literal)) => Err(ErrorType::InvalidLiteral { literal, span }),
+        }
     }
 
-    /// Reads an identifier from the input.
+    /// Reads the characters of a string or char literal up to its closing quote, resolving escape sequences.
+    ///     Literals end at the end of their line, and one that isn't closed by then is unterminated
+    fn read_quoted(&mut self, quote: char) -> Result<Vec<char>, LexError> {
+        let mut characters: Vec<char> = Vec::new();
+        let mut error: Option<LexError> = None;
+
+        loop {
+            if self.peek_char() == '\n' || self.position + 1 >= self.input.len() {
+                return Err(LexError::Unterminated);
+            }
+            self.read_char();
+            match self.current {
+                current if current == quote => break,
+                '\\' => match self.read_escape() {
+                    Ok(character) => characters.push(character),
+                    Err(e) => { error.get_or_insert(e); },
+                },
+                current => characters.push(current),
+            }
+        }
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(characters),
+        }
+    }
+
+    /// Reads the escape sequence starting at the current '\\', returning the character it stands for.
+    ///     Supported escapes are \\n, \\t, \\r, \\0, \\\\, \\", \\' and \\u{...} with a hexadecimal code point
+    fn read_escape(&mut self) -> Result<char, LexError> {
+        if self.peek_char() == '\n' || self.position + 1 >= self.input.len() {
+            return Err(LexError::InvalidEscape("\\".to_string()));
+        }
+        self.read_char();
+        match self.current {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            '0' => Ok('\0'),
+            '\\' | '"' | '\'' => Ok(self.current),
+            'u' if self.peek_char() == '{' => {
+                self.read_char();
+                let mut digits: String = String::new();
+                while self.peek_char().is_ascii_hexdigit() {
+                    self.read_char();
+                    digits.push(self.current);
+                }
+                let sequence: String = format!("\\u{{{}}}", digits);
+                if self.peek_char() != '}' {
+                    return Err(LexError::InvalidEscape(sequence));
+                }
+                self.read_char();
+                u32::from_str_radix(&digits, 16).ok()
+                    .and_then(char::from_u32)
+                    .ok_or(LexError::InvalidEscape(sequence))
+            },
+            current => Err(LexError::InvalidEscape(format!("\\{}", current))),
+        }
+    }
+
+    /// Reads an identifier from the input. Identifiers start with a letter and may contain digits after it, as in `U8`
     fn read_identifier(&mut self) -> Vec<char> {
-        self.read_while(is_letter)
+        self.read_while(|current| is_letter(current) || is_digit(current))
     }
 
-    /// Reads a number from the input.
-    fn read_number(&mut self) -> Vec<char> {
-        self.read_while(is_digit)
+    /// Reads a number: an integer in decimal, hexadecimal ('0x'), octal ('0o') or binary ('0b') with an optional 
+    ///     type suffix such as 'u8', or a decimal float. '_' may separate digits and is dropped, and integers 
+    ///     are given as decimal digits whose value must fit their type
+    fn read_number(&mut self) -> Result<Token, LexError> {
+        let radix: u32 = match (self.current, self.peek_char()) {
+            ('0', 'x') => 16,
+            ('0', 'o') => 8,
+            ('0', 'b') => 2,
+            _ => 10,
+        };
+        let mut number: Vec<char> = Vec::new();
+        let mut literal: Vec<char> = vec![self.current];
+
+        if radix == 10 {
+            number.push(self.current);
+            number.extend(self.read_digits(is_digit, &mut literal));
+        } else {
+            self.read_char();
+            literal.push(self.current);
+            // suffixes start with 'i' or 'u', which aren't digits in any radix
+            number = self.read_digits(|current| current.is_ascii_alphanumeric() && !matches!(current, 'i' | 'u'), &mut literal);
+        }
+
+        if radix == 10 {
+            let mut is_float: bool = false;
+
+            // a '.' is only part of the number if a digit follows it
+            if self.peek_char() == '.' && is_digit(self.peek_nth_char(2)) {
+                self.read_char();
+                literal.push('.');
+                number.push('.');
+                number.extend(self.read_digits(is_digit, &mut literal));
+                is_float = true;
+            }
+
+            let signed_exponent: bool = matches!(self.peek_nth_char(2), '+' | '-') && is_digit(self.peek_nth_char(3));
+            if matches!(self.peek_char(), 'e' | 'E') && (is_digit(self.peek_nth_char(2)) || signed_exponent) {
+                self.read_char();
+                literal.push(self.current);
+                number.push('e');
+                if signed_exponent {
+                    self.read_char();
+                    literal.push(self.current);
+                    number.push(self.current);
+                }
+                number.extend(self.read_digits(is_digit, &mut literal));
+                is_float = true;
+            }
+
+            if is_float {
+                return Ok(Token::FLOAT(number));
+            }
+        }
+
+        let mut suffix: Vec<char> = Vec::new();
+        if matches!(self.peek_char(), 'i' | 'u') {
+            suffix = self.read_digits(|current| current.is_ascii_alphanumeric(), &mut literal);
+        }
+
+        let invalid = || LexError::InvalidLiteral(literal.iter().collect());
+        let digits: String = number.iter().collect();
+        let value: u64 = u64::from_str_radix(&digits, radix).map_err(|_| invalid())?;
+        if suffix.is_empty() {
+            if value > i64::MAX as u64 {
+                return Err(invalid());
+            }
+            return Ok(Token::INT(value.to_string().chars().collect()));
+        }
+
+        let data_type: DataType = DataType::from_suffix(&suffix.iter().collect::<String>()).ok_or_else(invalid)?;
+        let bits: u32 = data_type.integer_width().unwrap_or(64);
+        let max: u64 = if data_type.is_unsigned() { u64::MAX >> (64 - bits) } else { u64::MAX >> (65 - bits) };
+        if value > max {
+            return Err(invalid());
+        }
+        Ok(Token::TYPEDINT(value.to_string().chars().collect(), suffix))
     }
 
+    /// Reads the characters after the current one that match the predicate, allowing '_' between them. 
+    ///     Returns the matched characters without separators, and records everything read in `literal`
+    fn read_digits<F>(&mut self, predicate: F, literal: &mut Vec<char>) -> Vec<char>
+        where
+            F: Fn(char) -> bool,
+        {
+            let mut digits: Vec<char> = Vec::new();
+            while predicate(self.peek_char()) || self.peek_char() == '_' {
+                self.read_char();
+                literal.push(self.current);
+                if self.current != '_' {
+                    digits.push(self.current);
+                }
+            }
+            digits
+        }
+
     /// Gives the next character without changing the position
     fn peek_char(&self) -> char {
-        if self.position + 1 >= self.input.len() {
+        self.peek_nth_char(1)
+    }
+
+    /// Gives the character `n` places ahead without changing the position
+    fn peek_nth_char(&self, n: usize) -> char {
+        if self.position + n >= self.input.len() {
             '~' // EOF token
         } else {
-            self.input[self.position + 1]
+            self.input[self.position + n]
         }
     }
     
 
-    /// Reads characters from the input while the given predicate is true.
+    /// Reads characters from the input while the given predicate is true. Leaves the lexer on the
+    ///     last character that matched, which is consumed by `next_token`
     fn read_while<F>(&mut self, predicate: F) -> Vec<char>
         where
             F: Fn(char) -> bool,
         {
             let start_pos = self.position;
-            while self.position < self.input.len() && predicate(self.current) {
+            while predicate(self.peek_char()) {
                 self.read_char();
             }
-            self.position = self.position - 1; // hacky solution, fix later
             self.input[start_pos..=self.position].to_vec() 
         }
 }
@@ -229,32 +533,40 @@ fn is_digit(current: char) -> bool {
     '0' <= current && current <= '9'
 }
 
-/// retrieves a token if text matches, else error
-fn get_token(raw_text: &Vec<char>) -> Result<Token, ErrorType> {
-    let identifier: String = raw_text.into_iter().collect();
+/// retrieves a token if text matches a keyword
+fn get_token(raw_text: &[char]) -> Option<Token> {
+    let identifier: String = raw_text.iter().collect();
     match &identifier[..] {
-        "let" => Ok(Token::LET),
-        "true" => Ok(Token::TRUE),
-        "false" => Ok(Token::FALSE),
-        "if" => Ok(Token::IF),
-        "else" => Ok(Token::ELSE),
-        "return" => Ok(Token::RETURN),
-        "Integer" => Ok(Token::TINTEGER),
-        "Float" => Ok(Token::TFLOAT),
-        "Boolean" => Ok(Token::TBOOLEAN),
-        "fn" => Ok(Token::FUNCTION),
-        "struct" => Ok(Token::STRUCT),
-        "enum" => Ok(Token::ENUM),
-        "String" => Ok(Token::TSTRING),
-        "Void" => Ok(Token::TVOID),
-        "Char" => Ok(Token::TCHAR),
-        "elif" => Ok(Token::ELIF),
-        "for" => Ok(Token::FOR),
-        "break" => Ok(Token::BREAK),
-        "do" => Ok(Token::DO),
-        "while" => Ok(Token::WHILE),
-        "match" => Ok(Token::MATCH),
-        "continue" => Ok(Token::CONTINUE),
-        _ => Err(ErrorType::UnrecognizedToken { token: String::from("Unrecognized token") }),
+        "let" => Some(Token::LET),
+        "true" => Some(Token::TRUE),
+        "false" => Some(Token::FALSE),
+        "if" => Some(Token::IF),
+        "else" => Some(Token::ELSE),
+        "return" => Some(Token::RETURN),
+        "Integer" => Some(Token::TINTEGER),
+        "I8" => Some(Token::TI8),
+        "I16" => Some(Token::TI16),
+        "I32" => Some(Token::TI32),
+        "I64" => Some(Token::TI64),
+        "U8" => Some(Token::TU8),
+        "U16" => Some(Token::TU16),
+        "U32" => Some(Token::TU32),
+        "U64" => Some(Token::TU64),
+        "Float" => Some(Token::TFLOAT),
+        "Boolean" => Some(Token::TBOOLEAN),
+        "fn" => Some(Token::FUNCTION),
+        "struct" => Some(Token::STRUCT),
+        "enum" => Some(Token::ENUM),
+        "String" => Some(Token::TSTRING),
+        "Void" => Some(Token::TVOID),
+        "Char" => Some(Token::TCHAR),
+        "elif" => Some(Token::ELIF),
+        "for" => Some(Token::FOR),
+        "break" => Some(Token::BREAK),
+        "do" => Some(Token::DO),
+        "while" => Some(Token::WHILE),
+        "match" => Some(Token::MATCH),
+        "continue" => Some(Token::CONTINUE),
+        _ => None,
     }
 }
\ No newline at end of file
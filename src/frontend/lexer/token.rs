@@ -1,5 +1,9 @@
+use std::fmt;
+
+use crate::frontend::utils::span::Span;
+
 /// Defines acceptable tokens in the program
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum Token {
     /// End of file
     EOF,
@@ -27,9 +31,24 @@ pub enum Token {
     /// Integer
     INT(Vec<char>),
 
+    /// Integer with a type suffix, holding its decimal digits and the suffix
+    TYPEDINT(Vec<char>, Vec<char>),
+
+    /// Float
+    FLOAT(Vec<char>),
+
     /// Identifier
     IDENTIFIER(Vec<char>),
 
+    /// String literal, with escapes resolved
+    STRING(Vec<char>),
+
+    /// Character literal, with escapes resolved
+    CHAR(char),
+
+    /// Doc comment ('///'), holding the text after the slashes
+    DOCCOMMENT(Vec<char>),
+
     /// --- BINARY OP SECTION --- ///
     /// Division
     DIVIDE,
@@ -52,8 +71,8 @@ pub enum Token {
     /// Multiply
     MULTIPLY,
 
-    /// Exponent
-    EXPONENT,
+    /// Bitwise exclusive or
+    BITWISEXOR,
 
     /// --- SCOPE CHANGING SECTION --- ///
     /// Function 
@@ -170,6 +189,30 @@ pub enum Token {
     /// Integer type
     TINTEGER,
 
+    /// Signed 8-bit integer type
+    TI8,
+
+    /// Signed 16-bit integer type
+    TI16,
+
+    /// Signed 32-bit integer type
+    TI32,
+
+    /// Signed 64-bit integer type, the same as Integer
+    TI64,
+
+    /// Unsigned 8-bit integer type
+    TU8,
+
+    /// Unsigned 16-bit integer type
+    TU16,
+
+    /// Unsigned 32-bit integer type
+    TU32,
+
+    /// Unsigned 64-bit integer type
+    TU64,
+
     /// Float type
     TFLOAT,
 
@@ -184,4 +227,117 @@ pub enum Token {
 
     /// Void type
     TVOID,
-}
\ No newline at end of file
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text: &str = match self {
+            Token::EOF => "end of file",
+            Token::LET => "let",
+            Token::PLUSASSIGN => "+=",
+            Token::MINUSASSIGN => "-=",
+            Token::MULTIPLYASSIGN => "*=",
+            Token::DIVIDEASSIGN => "/=",
+            Token::MODASSIGN => "%=",
+            Token::INT(value) => return write!(f, "{}", value.iter().collect::<String>()),
+            Token::TYPEDINT(value, suffix) => return write!(f, "{}{}", value.iter().collect::<String>(), suffix.iter().collect::<String>()),
+            Token::FLOAT(value) => return write!(f, "{}", value.iter().collect::<String>()),
+            Token::IDENTIFIER(name) => return write!(f, "{}", name.iter().collect::<String>()),
+            Token::STRING(value) => return write!(f, "\"{}\"", value.iter().collect::<String>()),
+            Token::CHAR(value) => return write!(f, "'{}'", value),
+            Token::DOCCOMMENT(text) => return write!(f, "///{}", text.iter().collect::<String>()),
+            Token::DIVIDE => "/",
+            Token::FLOORDIVISION => "//",
+            Token::MINUS => "-",
+            Token::PLUS => "+",
+            Token::EQUAL => "=",
+            Token::MOD => "%",
+            Token::MULTIPLY => "*",
+            Token::BITWISEXOR => "^",
+            Token::FUNCTION => "fn",
+            Token::STRUCT => "struct",
+            Token::ENUM => "enum",
+            Token::IF => "if",
+            Token::ELIF => "elif",
+            Token::ELSE => "else",
+            Token::RETURN => "return",
+            Token::FOR => "for",
+            Token::WHILE => "while",
+            Token::DO => "do",
+            Token::BREAK => "break",
+            Token::CONTINUE => "continue",
+            Token::MATCH => "match",
+            Token::ARROW => "=>",
+            Token::RBRACKET => "}",
+            Token::LBRACKET => "{",
+            Token::LPAREN => "(",
+            Token::RPAREN => ")",
+            Token::SEMICOLON => ";",
+            Token::COMMA => ",",
+            Token::COLON => ":",
+            Token::LBRACE => "[",
+            Token::RBRACE => "]",
+            Token::DOT => ".",
+            Token::COLONCOLON => "::",
+            Token::LOGICALAND => "&&",
+            Token::LOGICALOR => "||",
+            Token::LOGICALNOT => "!",
+            Token::TRUE => "true",
+            Token::FALSE => "false",
+            Token::LESSTHAN => "<",
+            Token::GREATERTHAN => ">",
+            Token::NOTEQUAL => "!=",
+            Token::EQUALEQUAL => "==",
+            Token::LESSTHANEQUAL => "<=",
+            Token::GREATERTHANEQUAL => ">=",
+            Token::TINTEGER => "Integer",
+            Token::TI8 => "I8",
+            Token::TI16 => "I16",
+            Token::TI32 => "I32",
+            Token::TI64 => "I64",
+            Token::TU8 => "U8",
+            Token::TU16 => "U16",
+            Token::TU32 => "U32",
+            Token::TU64 => "U64",
+            Token::TFLOAT => "Float",
+            Token::TBOOLEAN => "Boolean",
+            Token::TSTRING => "String",
+            Token::TCHAR => "Char",
+            Token::TVOID => "Void",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// A token along with its location in the source code
+#[derive(PartialEq, Debug, Clone)]
+pub struct SpannedToken {
+    token: Token,
+    span: Span,
+}
+
+impl SpannedToken {
+    /// Creates a new spanned token
+    pub fn new(token: Token, span: Span) -> Self {
+        Self {
+            token,
+            span,
+        }
+    }
+
+    /// Retrieves the token
+    pub fn get_token(&self) -> Token {
+        self.token.clone()
+    }
+
+    /// Retrieves the location of the token
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+}
+
+impl From<Token> for SpannedToken {
+    fn from(token: Token) -> Self {
+        SpannedToken::new(token, Span::default())
+    }
+}
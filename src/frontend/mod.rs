@@ -14,4 +14,7 @@ pub mod utils;
 pub mod parser;
 
 /// Symbol Table Stack Utils and Generation
-pub mod symbol_table;
\ No newline at end of file
+pub mod symbol_table;
+
+/// Constant Folding
+pub mod const_fold;
\ No newline at end of file
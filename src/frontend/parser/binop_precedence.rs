@@ -4,15 +4,30 @@ Defines the precedence of binary operations
 
 use std::collections::HashMap;
 
-pub fn binop_precedence() -> &'static once_cell::sync::Lazy<HashMap<char, i32>> {
-    static PRECEDENCE: once_cell::sync::Lazy<HashMap<char, i32>> = 
+use crate::frontend::lexer::token::Token;
+
+/// Precedence of unary operators. Binds tighter than every binary operator
+pub const UNARY_PRECEDENCE: i32 = 45;
+
+pub fn binop_precedence() -> &'static once_cell::sync::Lazy<HashMap<Token, i32>> {
+    static PRECEDENCE: once_cell::sync::Lazy<HashMap<Token, i32>> = 
     once_cell::sync::Lazy::new(|| {
         let mut p = HashMap::new();
-        p.insert('<', 10);
-        p.insert('+', 20);
-        p.insert('-', 20);
-        p.insert('*', 40);
+        p.insert(Token::LOGICALOR, 5);
+        p.insert(Token::LOGICALAND, 6);
+        p.insert(Token::EQUALEQUAL, 10);
+        p.insert(Token::NOTEQUAL, 10);
+        p.insert(Token::LESSTHAN, 15);
+        p.insert(Token::GREATERTHAN, 15);
+        p.insert(Token::LESSTHANEQUAL, 15);
+        p.insert(Token::GREATERTHANEQUAL, 15);
+        p.insert(Token::BITWISEXOR, 17);
+        p.insert(Token::PLUS, 20);
+        p.insert(Token::MINUS, 20);
+        p.insert(Token::MULTIPLY, 40);
+        p.insert(Token::DIVIDE, 40);
+        p.insert(Token::MOD, 40);
         p
     });
     &PRECEDENCE
-}
\ No newline at end of file
+}
@@ -5,6 +5,7 @@ use crate::frontend::{
         ast_struct::ASTNode, syntax_element::{
             SyntaxElement, MatchArm
         }, 
+        data_type::DataType,
     },
     parser::parser_core::Parser,
 };
@@ -15,20 +16,18 @@ impl Parser {
         let mut children: Vec<ASTNode> = Vec::new();
         self.consume_token(Token::LBRACKET)?; 
 
-        while self.get_current() < self.get_input().len() && self.get_input().get(self.get_current()) != Some(&Token::RBRACKET) {
+        while self.get_current() < self.get_input().len() && 
+            !matches!(self.get_input().get(self.get_current()), Some(Token::RBRACKET) | Some(Token::EOF)) {
+            let start: usize = self.get_current();
             match self.parse_router() {
                 Ok(Some(expr_node)) => {
                     children.push(expr_node);
                 }
                 Ok(None) => {}
-                _ => panic!("parse_block parse problem")
+                Err(error_types) => self.recover(start, error_types),
             }
         }
-        if self.get_input().get(self.get_current()) == Some(&Token::RBRACKET) {
-            self.consume_token(Token::RBRACKET)?;
-        } else {
-            panic!("failed to reach stop token")
-        }
+        self.consume_token(Token::RBRACKET)?;
         Ok(children)
     }
 
@@ -40,40 +39,28 @@ impl Parser {
                 Some(Token::LET) => {
                     self.consume_token(Token::LET)?;
     
-                    let variable_name = if let Some(Token::IDENTIFIER(name_chars)) = self.get_input().get(self.get_current()) {
-                        self.consume_token(Token::IDENTIFIER(name_chars.clone()))?;
-                        name_chars.iter().collect::<String>()
+                    let variable_name: String = self.consume_identifier("a variable name")?;
+
+                    // the annotation is optional, its type is inferred during semantic analysis otherwise
+                    let data_type: DataType = if let Some(Token::COLON) = self.get_input().get(self.get_current()) {
+                        self.consume_token(Token::COLON)?;
+                        self.consume_type()?
                     } else {
-                        panic!("wheres my variable name?")
+                        DataType::Unknown
                     };
-                    self.consume_token(Token::COLON)?;
-                
-                    let data_type = self.consume_type()?;
-                    if let Some(Token::EQUAL) = self.get_input().get(self.get_current()) {
-                        self.consume_token(Token::EQUAL)?;
-                    } else {
-                        panic!("wheres my assignment operator?")
-                    }
+                    self.consume_token(Token::EQUAL)?;
     
-                    let value: ASTNode = match self.parse_router() {
-                        Ok(Some(value)) => value, 
-                        Ok(None) => {
-                            panic!("initialization value is missing");
-                        }
-                        Err(_) => {
-                            panic!("Failed to parse initialization value");
-                        }
-                    };    
+                    let value: ASTNode = self.parse_expression()?;
                     Ok(Some(ASTNode::new(SyntaxElement::Initialization {
                         variable: variable_name,
                         data_type,
                         value: Box::new(value),
                     })))
                 },
-                _ => panic!("how'd you reach this let panic? this is a tricky one"),
+                _ => Err(vec![self.syntax_error("'let'")]),
             }
         } else {
-            panic!("parse_initilization")
+            Err(vec![self.syntax_error("'let'")])
         }
     }
 
@@ -85,15 +72,7 @@ impl Parser {
                 Some(Token::MATCH) => {
                     self.consume_token(Token::MATCH)?;
 
-                    let value: ASTNode = match self.parse_router() {
-                        Ok(Some(value)) => value, 
-                        Ok(None) => {
-                            panic!("Assignment value is missing");
-                        }
-                        Err(_) => {
-                            panic!("Failed to parse assignment value");
-                        }
-                    };
+                    let value: ASTNode = self.parse_expression()?;
                     let to_match: Box<ASTNode> = Box::new(value);
                     let arms: Vec<MatchArm> = self.parse_match_arms()?;
                     let match_node = ASTNode::new(SyntaxElement::MatchStatement {
@@ -102,50 +81,38 @@ impl Parser {
                     });
                     return Ok(Some(match_node));
                 }
-                _ => panic!("problem match parsing"),
+                _ => return Err(vec![self.syntax_error("'match'")]),
             }
-        } panic!("problem matach parsing 2")
+        } Err(vec![self.syntax_error("'match'")])
     }
 
     /// Parses the match arms of a match statement
+    /// current format: { 1 => { ... }, Color::Red => { ... }, _ => { ... } }
     pub fn parse_match_arms(&mut self) -> Result<Vec<MatchArm>, Vec<ErrorType>> { 
         let mut arms: Vec<MatchArm> = Vec::new();
 
         self.consume_token(Token::LBRACKET)?;
 
         while self.get_current() < self.get_input().len() && self.get_input().get(self.get_current()) != Some(&Token::RBRACKET) {
-            let variant: ASTNode = match self.parse_router() {
-                Ok(Some(value)) => value, 
-                Ok(None) => {
-                    panic!("Assignment value is missing");
-                }
-                Err(_) => {
-                    panic!("Failed to parse assignment value");
-                }
+            let variant: ASTNode = match self.get_input().get(self.get_current()) {
+                Some(Token::IDENTIFIER(name)) if *name == ['_'] => {
+                    let start: usize = self.get_current();
+                    self.consume_identifier("'_'")?;
+                    ASTNode::with_span(SyntaxElement::Wildcard, self.span_from(start))
+                },
+                _ => self.parse_expression()?,
             };
 
             self.consume_token(Token::ARROW)?;  
 
-            self.consume_token(Token::LBRACKET)?;
-
-            let action: ASTNode = match self.parse_router() {
-                Ok(Some(value)) => value, 
-                Ok(None) => {
-                    panic!("Assignment value is missing");
-                }
-                Err(_) => {
-                    panic!("Failed to parse assignment value");
-                }
-            };
-
-            self.consume_token(Token::RBRACKET)?;
+            let action: Vec<ASTNode> = self.parse_block()?;
 
             arms.push(MatchArm::new(variant, action));
 
             if let Some(Token::COMMA) = self.get_input().get(self.get_current()) {
                 self.consume_token(Token::COMMA)?;
             } else if self.get_input().get(self.get_current()) != Some(&Token::RBRACKET) {
-                panic!("match arms problem")
+                return Err(vec![self.syntax_error("',' or '}'")]);
             }
         }
         self.consume_token(Token::RBRACKET)?;
@@ -154,42 +121,52 @@ impl Parser {
     }
 
     /// Parses an if statement
-    /// current if statement form: if(condition) {}
+    /// current if statement form: if(condition) {} elif(condition) {} else {}
     pub fn parse_if_statement(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         if self.get_current() < self.get_input().len() {
             match self.get_input().get(self.get_current()) {
                 Some(Token::IF) => {
                     self.consume_token(Token::IF)?;
-                    self.consume_token(Token::LPAREN)?;
-                    
-                    let condition: ASTNode = match self.parse_router() {
-                        Ok(Some(value)) => {value}
-                        _ => panic!("if statement panic")
-                    };
-                    self.consume_token(Token::RPAREN)?;
-
-                    let then_branch: Vec<ASTNode> = self.parse_block()?;
-                    let else_branch: Option<Box<Vec<ASTNode>>> = if let Some(Token::ELSE) = self.get_input().get(self.get_current()) {
-                        self.consume_token(Token::ELSE)?;
-                        Some(Box::new(self.parse_block()?))
-                        } else {
-                        None
-                    };
-
-                    let if_node: ASTNode = ASTNode::new(SyntaxElement::IfStatement { 
-                        condition: Box::new(condition), 
-                        then_branch: Box::new(then_branch), 
-                        else_branch,
-                    });
-                    return Ok(Some(if_node));
+                    return Ok(Some(self.parse_conditional_branches()?));
                 }
-                _ => panic!("Problem parsing in if statement"),
+                _ => return Err(vec![self.syntax_error("'if'")]),
             }
-        } panic!("Problem parsing if statement 2")
+        } Err(vec![self.syntax_error("'if'")])
+    }
+
+    /// Parses the condition and branches that follow an 'if' or 'elif'. An elif is desugared into an if statement
+    ///     that is the only statement of the else branch
+    fn parse_conditional_branches(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        self.consume_token(Token::LPAREN)?;
+        
+        let condition: ASTNode = self.parse_expression()?;
+        self.consume_token(Token::RPAREN)?;
+
+        let then_branch: Vec<ASTNode> = self.parse_block()?;
+        let else_branch: Option<Box<Vec<ASTNode>>> = match self.get_input().get(self.get_current()) {
+            Some(Token::ELSE) => {
+                self.consume_token(Token::ELSE)?;
+                Some(Box::new(self.parse_block()?))
+            },
+            Some(Token::ELIF) => {
+                let start: usize = self.get_current();
+                self.consume_token(Token::ELIF)?;
+                let mut elif_node: ASTNode = self.parse_conditional_branches()?;
+                elif_node.set_span(self.span_from(start));
+                Some(Box::new(vec![elif_node]))
+            },
+            _ => None,
+        };
+
+        Ok(ASTNode::new(SyntaxElement::IfStatement { 
+            condition: Box::new(condition), 
+            then_branch: Box::new(then_branch), 
+            else_branch,
+        }))
     }
 
     /// Parses a for loop
-    /// current format: for (let i: int = 0; i < 1; i += 1;) {}
+    /// current format: for (let i: Integer = 0; i < 1; i += 1;) {}
     pub fn parse_for_loop(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         if self.get_current() < self.get_input().len() {
             match self.get_input().get(self.get_current()) {
@@ -197,39 +174,22 @@ impl Parser {
                     self.consume_token(Token::FOR)?;
                     self.consume_token(Token::LPAREN)?;
         
-                    // let initializer: Option<Box<ASTNode>> = if self.get_input().get(self.get_current()) != Some(&Token::RPAREN) {
-                    //     match self.parse_router() {
-                    //         Ok(node) => {
-                    //             self.consume_token(Token::SEMICOLON)?;
-                    //             node.map(Box::new)
-
-                    //         },
-                    //         _ => panic!("for loop")
-                    //     }
-                    // } else {
-                    //     None
-                    // };
-                    let condition: Box<ASTNode> = if self.get_input().get(self.get_current()) != Some(&Token::RPAREN) {
-                        match self.parse_router() {
-                            Ok(Some(node)) => {
-                                self.consume_token(Token::SEMICOLON)?;
-                                Box::new(node)
-                            },
-                            _ => panic!("for loop condition?"),
-                        }
+                    // the initializer is optional, a loop without one starts directly with its condition
+                    let initializer: Option<Box<ASTNode>> = if let Some(Token::LET) = self.get_input().get(self.get_current()) {
+                        let node: Option<ASTNode> = self.parse_router()?;
+                        self.consume_token(Token::SEMICOLON)?;
+                        node.map(Box::new)
                     } else {
-                        panic!("For loop 9");
+                        None
                     };
+                    let condition: Box<ASTNode> = Box::new(self.parse_expression()?);
+                    self.consume_token(Token::SEMICOLON)?;
                     
                     
                     let increment: Option<Box<ASTNode>> = if self.get_input().get(self.get_current()) != Some(&Token::RPAREN) {
-                        match self.parse_router() {
-                            Ok(node) => {
-                                self.consume_token(Token::SEMICOLON)?;
-                                node.map(Box::new)
-                            }
-                            _ => panic!("for loop 2")
-                        }
+                        let node: Option<ASTNode> = self.parse_router()?;
+                        self.consume_token(Token::SEMICOLON)?;
+                        node.map(Box::new)
                     } else {
                         None
                     };
@@ -239,8 +199,7 @@ impl Parser {
                     let body: Box<Vec<ASTNode>> = Box::new(self.parse_block()?);
 
                     let for_node: ASTNode = ASTNode::new(SyntaxElement::ForLoop {
-                        // initializer, 
-                        initializer: None,
+                        initializer,
                         condition,   
                         increment,
                         body,
@@ -248,9 +207,9 @@ impl Parser {
         
                     return Ok(Some(for_node));
                 }
-                _ => panic!("Problem parsing in for loop"),
+                _ => return Err(vec![self.syntax_error("'for'")]),
             }
-        } panic!("Problem parsing for loop 2")
+        } Err(vec![self.syntax_error("'for'")])
     }
 
     /// Parses a while loop
@@ -262,15 +221,7 @@ impl Parser {
                     self.consume_token(Token::WHILE)?;
                     self.consume_token(Token::LPAREN)?;
 
-                    let condition: Box<ASTNode> = Box::new(match self.parse_router() {
-                        Ok(Some(value)) => value, 
-                        Ok(None) => {
-                            panic!("while1");
-                        }
-                        Err(_) => {
-                            panic!("while2");
-                        }
-                    });
+                    let condition: Box<ASTNode> = Box::new(self.parse_expression()?);
                     self.consume_token(Token::RPAREN)?;
                     let body: Box<Vec<ASTNode>> = Box::new(self.parse_block()?);
 
@@ -280,9 +231,9 @@ impl Parser {
                     });
                     return Ok(Some(while_node));
                 } 
-                _ => panic!("problem while loop parsing"),
+                _ => return Err(vec![self.syntax_error("'while'")]),
             }
-        } panic!("problem while loop parsing 2")
+        } Err(vec![self.syntax_error("'while'")])
     }
 
     /// Parses a do while loop
@@ -297,10 +248,7 @@ impl Parser {
 
                     self.consume_token(Token::WHILE)?;
                     self.consume_token(Token::LPAREN)?;
-                    let value: ASTNode = match self.parse_router() {
-                        Ok(Some(node_option)) => node_option,
-                        _ => panic!("missing while condition"), 
-                    };
+                    let value: ASTNode = self.parse_expression()?;
                     let condition: Box<ASTNode> = Box::new(value);
                     self.consume_token(Token::RPAREN)?;
                     let do_while_node = ASTNode::new(SyntaxElement::DoWhileLoop {
@@ -309,8 +257,8 @@ impl Parser {
                     });
                     return Ok(Some(do_while_node));
                 }
-                _ => panic!("problem do_while parsing"),
+                _ => return Err(vec![self.syntax_error("'do'")]),
             }
-        } panic!("problem do_while parsing 2")
+        } Err(vec![self.syntax_error("'do'")])
     }
 }
\ No newline at end of file
@@ -4,12 +4,157 @@ use crate::frontend::{
     ast::{
         ast_struct::ASTNode, 
         syntax_element::SyntaxElement,
+        data_type::DataType,
     },
     parser::parser_core::Parser,
-    parser::binop_precedence::binop_precedence,
+    parser::binop_precedence::{binop_precedence, UNARY_PRECEDENCE},
 };
 
 impl Parser {
+    /// Parses an expression made of operands and unary/binary operators
+    pub fn parse_expression(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        self.parse_expression_with_precedence(0)
+    }
+
+    /// Parses an expression using precedence climbing. Only binary operators that bind at least as 
+    ///     tightly as `min_precedence` are consumed, the rest are left for the caller
+    fn parse_expression_with_precedence(&mut self, min_precedence: i32) -> Result<ASTNode, Vec<ErrorType>> {
+        let mut lhs: ASTNode = self.parse_operand()?;
+
+        while let Some(op_token) = self.get_input().get(self.get_current()).cloned() {
+            let precedence: i32 = match binop_precedence().get(&op_token) {
+                Some(&precedence) if precedence >= min_precedence => precedence,
+                _ => break,
+            };
+            let operator: String = match self.operator_to_str(&op_token) {
+                Some(operator) => operator.to_string(),
+                None => break,
+            };
+            self.consume_token(op_token.clone())?;
+
+            // every binary operator groups from the left
+            let rhs: ASTNode = self.parse_expression_with_precedence(precedence + 1)?;
+
+            let span = lhs.get_span().merge(&rhs.get_span());
+            lhs = ASTNode::with_span(SyntaxElement::BinaryExpression {
+                left: Box::new(lhs),
+                operator,
+                right: Box::new(rhs),
+            }, span);
+        }
+        Ok(lhs)
+    }
+
+    /// Parses a single operand of an expression: a literal, variable, function call, enum variant, struct literal, 
+    ///     parenthesized expression or unary expression, followed by any field accesses
+    fn parse_operand(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        let start: usize = self.get_current();
+        let operand: Option<ASTNode> = match self.get_input().get(self.get_current()) {
+            Some(Token::MINUS) | Some(Token::LOGICALNOT) => self.parse_unary_expression()?,
+            Some(Token::INT(_)) | Some(Token::TYPEDINT(_, _)) | Some(Token::FLOAT(_)) | Some(Token::STRING(_)) | Some(Token::CHAR(_)) 
+            | Some(Token::TRUE) | Some(Token::FALSE) => self.parse_primitive()?,
+            Some(Token::IDENTIFIER(_)) => {
+                if let Some(Token::LPAREN) = self.peek_token() {
+                    Some(self.parse_function_call()?)
+                } else if let Some(Token::COLONCOLON) = self.peek_token() {
+                    Some(self.parse_enum_variant()?)
+                } else if self.is_struct_literal() {
+                    Some(self.parse_struct_literal()?)
+                } else {
+                    let name: String = self.consume_identifier("an identifier")?;
+                    Some(ASTNode::new(SyntaxElement::Variable { data_type: DataType::Unknown, name }))
+                }
+            }
+            Some(Token::LPAREN) => {
+                self.consume_token(Token::LPAREN)?;
+                let inner: ASTNode = self.parse_expression()?;
+                self.consume_token(Token::RPAREN)?;
+                Some(inner)
+            }
+            _ => None,
+        };
+        let mut node: ASTNode = match operand {
+            Some(mut node) => {
+                node.set_span(self.span_from(start));
+                node
+            }
+            None => return Err(vec![self.syntax_error("an expression")]),
+        };
+
+        // field accesses bind tighter than any operator, and can be chained
+        while let Some(Token::DOT) = self.get_input().get(self.get_current()) {
+            self.consume_token(Token::DOT)?;
+            let field: String = self.consume_identifier("a field name")?;
+            node = ASTNode::with_span(SyntaxElement::FieldAccess { base: Box::new(node), field }, self.span_from(start));
+        }
+        Ok(node)
+    }
+
+    /// Checks if the upcoming tokens start a struct literal, a name followed by '{' and the first field. Blocks 
+    ///     that follow a name, like the arms of a match, never start with a field and a colon
+    fn is_struct_literal(&mut self) -> bool {
+        let input: Vec<Token> = self.get_input();
+        matches!(
+            (input.get(self.get_current() + 1), input.get(self.get_current() + 2), input.get(self.get_current() + 3)),
+            (Some(Token::LBRACKET), Some(Token::IDENTIFIER(_)), Some(Token::COLON))
+        )
+    }
+
+    /// Parses a struct literal
+    /// current format: Point { x: 1, y: 2 }
+    pub fn parse_struct_literal(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        let name: String = self.consume_identifier("a struct name")?;
+        self.consume_token(Token::LBRACKET)?;
+
+        let mut fields: Vec<(String, ASTNode)> = Vec::new();
+        while self.get_input().get(self.get_current()) != Some(&Token::RBRACKET) {
+            let field: String = self.consume_identifier("a field name")?;
+            self.consume_token(Token::COLON)?;
+            fields.push((field, self.parse_expression()?));
+
+            match self.get_input().get(self.get_current()) {
+                Some(Token::COMMA) => self.consume_token(Token::COMMA)?,
+                Some(Token::RBRACKET) => break,
+                _ => return Err(vec![self.syntax_error("',' or '}'")]),
+            }
+        }
+        self.consume_token(Token::RBRACKET)?;
+
+        Ok(ASTNode::new(SyntaxElement::StructLiteral { name, fields }))
+    }
+
+    /// Parses a function call and its arguments
+    /// current format: foo(a, b + 1, bar())
+    pub fn parse_function_call(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        let name: String = self.consume_identifier("a function name")?;
+        self.consume_token(Token::LPAREN)?;
+
+        let mut arguments: Vec<ASTNode> = Vec::new();
+        if self.get_input().get(self.get_current()) != Some(&Token::RPAREN) {
+            loop {
+                arguments.push(self.parse_expression()?);
+                match self.get_input().get(self.get_current()) {
+                    Some(Token::COMMA) => self.consume_token(Token::COMMA)?,
+                    Some(Token::RPAREN) => break,
+                    _ => return Err(vec![self.syntax_error("',' or ')'")]),
+                }
+            }
+        }
+        self.consume_token(Token::RPAREN)?;
+
+        Ok(ASTNode::new(SyntaxElement::FunctionCall { name, arguments }))
+    }
+
+    /// Parses a path naming an enum variant
+    /// current format: Color::Red
+    pub fn parse_enum_variant(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        let enum_name: String = self.consume_identifier("an enum name")?;
+        self.consume_token(Token::COLONCOLON)?;
+        let variant: String = self.consume_identifier("a variant name")?;
+
+        Ok(ASTNode::new(SyntaxElement::EnumVariant { enum_name, variant }))
+    }
+
     /// Parses a unary expression
     pub fn parse_unary_expression(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         if self.get_current() < self.get_input().len() {
@@ -24,53 +169,26 @@ impl Parser {
                             self.consume_token(Token::LOGICALNOT)?;
                             "!"
                         },
-                        _ => panic!("This was a hard panic to hit"),
+                        _ => return Err(vec![self.syntax_error("a unary operator")]),
                     }.to_string();
         
-                    let operand: ASTNode = match self.parse_router() {
-                        Ok(Some(value)) => value, 
-                        Ok(None) => {
-                            panic!("unary is missing");
-                        }
-                        Err(_) => {
-                            panic!("Failed to parse unary value");
-                        }
-                    };    
+                    let operand: ASTNode = self.parse_expression_with_precedence(UNARY_PRECEDENCE)?;
                     return Ok(Some(ASTNode::new(SyntaxElement::UnaryExpression {
                         operator,
                         operand: Box::new(operand),
                     })));
                 },
-                _ => panic!("Is this part of a unary expression?{:?}", self.get_input().get(self.get_current())),
+                _ => {}
             }
-        } panic!("how'd you hit this?")
+        } Err(vec![self.syntax_error("a unary operator")])
     }
     
     /// Parses a variable reassignment (variable is already initialized)
     pub fn parse_assignment(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
-        let variable_name: String = 
-            if let Some(Token::IDENTIFIER(name_chars)) = self.get_input().get(self.get_current()) {
-                self.consume_token(Token::IDENTIFIER(name_chars.clone()))?;
-                name_chars.iter().collect::<String>()
-            } else {
-                panic!("parse_assignment 1")
-            };
-    
-        if let Some(Token::EQUAL) = self.get_input().get(self.get_current()) {
-            self.consume_token(Token::EQUAL)?;
-        } else {
-            panic!("parse_assignment 2")
-        }
+        let variable_name: String = self.consume_identifier("a variable name")?;
+        self.consume_token(Token::EQUAL)?;
     
-        let value: ASTNode = match self.parse_router() { 
-            Ok(Some(value)) => value, 
-            Ok(None) => {
-                panic!("Assignment value is missing");
-            }
-            Err(_) => {
-                panic!("Failed to parse assignment value");
-            }
-        }; // value consumed with parse_router()
+        let value: ASTNode = self.parse_expression()?;
         
         Ok(Some(ASTNode::new(SyntaxElement::Assignment {
             variable: variable_name,
@@ -78,55 +196,4 @@ impl Parser {
         })))
         
     }
-    
-    /// Parses a binary expression
-    pub fn parse_binary_expression(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
-        let lhs: Option<ASTNode> = self.parse_router()?;
-        if let Some(lhs_unwrapped) = lhs {
-            let mut expr: Option<ASTNode> = None;
-            while let Some(op_token) = self.get_input().get(self.get_current()) {
-                if let Some(&precedence) = binop_precedence().get(&self.operator_to_char(op_token)) {
-                    self.consume_token(op_token.clone())?;
-    
-                    let mut rhs: Option<ASTNode> = self.parse_router()?; // i think this is a bug
-                    // TODO actually the whole function idk this needs work
-                    let operator: String = self.operator_to_char(op_token).to_string();
-    
-                    while let Some(_) = self.get_next_operator_with_higher_precedence(precedence.try_into().unwrap()) {
-                        rhs = match self.parse_binary_expression()? {
-                            Some(r) => Some(r),
-                            None => break,
-                        };
-                    }
-    
-                    expr = match rhs {
-                        Some(right_node) => {
-                            Some(ASTNode::new(SyntaxElement::BinaryExpression {
-                                left: Box::new(lhs_unwrapped.clone()),
-                                operator,
-                                right: Box::new(right_node),
-                            }))
-                        },
-                        None => None,
-                    };
-                } else {
-                    break;
-                }
-            }
-            Ok(expr)
-        } else {
-            Ok(None)
-        }
-    }
-
-    fn get_next_operator_with_higher_precedence(&mut self, current_precedence: usize) -> Option<Token> {
-        if let Some(next_op) = self.get_input().get(self.get_current()) {
-            if let Some(&next_precedence) = binop_precedence().get(&self.operator_to_char(next_op)) {
-                if current_precedence < next_precedence.try_into().unwrap() {
-                    return Some(next_op.clone());
-                }
-            }
-        }
-        None
-    }
-}
\ No newline at end of file
+}
@@ -19,6 +19,26 @@ impl Parser {
                     let value_str = value.iter().collect::<String>();
                     return Ok(Some(ASTNode::new(SyntaxElement::Literal{data_type: DataType::Integer, value: value_str})));
                 },
+                Some(Token::TYPEDINT(value, suffix)) => {
+                    self.consume_token(Token::TYPEDINT(value.clone(), suffix.clone()))?;
+                    let value_str = value.iter().collect::<String>();
+                    let data_type: DataType = DataType::from_suffix(&suffix.iter().collect::<String>()).unwrap_or(DataType::Integer);
+                    return Ok(Some(ASTNode::new(SyntaxElement::Literal{data_type, value: value_str})));
+                },
+                Some(Token::FLOAT(value)) => {
+                    self.consume_token(Token::FLOAT(value.clone()))?;
+                    let value_str = value.iter().collect::<String>();
+                    return Ok(Some(ASTNode::new(SyntaxElement::Literal{data_type: DataType::Float, value: value_str})));
+                },
+                Some(Token::STRING(value)) => {
+                    self.consume_token(Token::STRING(value.clone()))?;
+                    let value_str = value.iter().collect::<String>();
+                    return Ok(Some(ASTNode::new(SyntaxElement::Literal{data_type: DataType::String, value: value_str})));
+                },
+                Some(Token::CHAR(value)) => {
+                    self.consume_token(Token::CHAR(*value))?;
+                    return Ok(Some(ASTNode::new(SyntaxElement::Literal{data_type: DataType::Char, value: value.to_string()})));
+                },
                 Some(Token::TRUE) => {
                     self.consume_token(Token::TRUE)?;
                     return Ok(Some(ASTNode::new(SyntaxElement::Literal{data_type: DataType::Boolean, value: "true".to_string()})))
@@ -27,27 +47,49 @@ impl Parser {
                     self.consume_token(Token::FALSE)?;
                     return Ok(Some(ASTNode::new(SyntaxElement::Literal{data_type: DataType::Boolean, value: "false".to_string()})))
                 },
-                _ => panic!("{:?}", self.get_input().get(self.get_current()))
+                _ => {}
             }
-        } panic!("parse_primitive panic")
+        } Err(vec![self.syntax_error("a literal")])
     }
 
-    /// Parses an identifier
+    /// Parses a statement that starts with an identifier: an assignment, a field assignment, a compound assignment 
+    ///     or an expression. Compound assignments are desugared, so `x += 1` becomes `x = x + 1`
     pub fn parse_identifier(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
-        let input = self.get_input();
-        let name_chars: &Vec<char> = match input.get(self.get_current()) {
-            Some(Token::IDENTIFIER(name_chars)) => {
-                name_chars
-            }
-            _ => panic!("expected id")
+        if let Some(Token::EQUAL) = self.peek_token() {
+            return self.parse_assignment();
+        }
+        let start: usize = self.get_current();
+        let expression: ASTNode = self.parse_expression()?;
+
+        let operator: Option<&str> = match self.get_input().get(self.get_current()) {
+            Some(Token::EQUAL) => None,
+            Some(token) if compound_operator(token).is_some() => compound_operator(token),
+            _ => return Ok(Some(expression)),
         };
-        self.consume_token(Token::IDENTIFIER(name_chars.clone()))?;
-        match self.get_input().get(self.get_current()) {
-            Some(Token::EQUAL) => {
-                return self.parse_assignment();
-            }
-            _ => return Ok(Some(ASTNode::new(SyntaxElement::Variable { data_type: DataType::Unknown, name: name_chars.iter().collect() })))
-        } // need to expand this
+        if !expression.is_assignable() {
+            return Err(vec![ErrorType::InvalidAssignment { target: describe_target(&expression), span: self.span_from(start) }]);
+        }
+        let assign_token: Token = self.get_input()[self.get_current()].clone();
+        self.consume_token(assign_token)?;
+
+        let mut value: ASTNode = self.parse_expression()?;
+        if let Some(operator) = operator {
+            value = ASTNode::with_span(SyntaxElement::BinaryExpression { 
+                left: Box::new(expression.clone()), 
+                operator: operator.to_string(), 
+                right: Box::new(value),
+            }, self.span_from(start));
+        }
+
+        match expression.get_element() {
+            SyntaxElement::Variable { name, .. } => {
+                Ok(Some(ASTNode::new(SyntaxElement::Assignment { variable: name, value: Box::new(value) })))
+            },
+            SyntaxElement::FieldAccess { base, field } => {
+                Ok(Some(ASTNode::new(SyntaxElement::FieldAssignment { base, field, value: Box::new(value) })))
+            },
+            _ => unreachable!("only variables and fields are assignable"),
+        }
     }
 
     /// Parses a protected keyword
@@ -66,9 +108,9 @@ impl Parser {
                 }
                 Some(Token::RETURN) => {
                     self.consume_token(Token::RETURN)?;
-                    let value = match self.parse_router() {
-                        Ok(Some(value)) => {value}
-                        _ => panic!("return panic")
+                    let value: ASTNode = match self.get_input().get(self.get_current()) {
+                        Some(Token::SEMICOLON) => ASTNode::new(SyntaxElement::NoExpression),
+                        _ => self.parse_expression()?,
                     };
                     self.consume_token(Token::SEMICOLON)?;
                     return Ok(Some(ASTNode::new(SyntaxElement::Return{value: Box::new(value)})))
@@ -81,9 +123,9 @@ impl Parser {
                     self.consume_token(Token::EOF)?;
                     return Ok(None);
                 }
-                _ => panic!("Are you sure this is a protected keyword? {:?}", self.get_input().get(self.get_current()))
+                _ => {}
             }
-        } panic!("parse_protected_keyword panic")
+        } Err(vec![self.syntax_error("a keyword")])
     }
 
     /// Consumes a type token
@@ -98,11 +140,68 @@ impl Parser {
                     self.consume_token(Token::TBOOLEAN)?;
                     Ok(DataType::Boolean)
                 }  
-                _ => panic!("not a type"),
+                Token::TFLOAT => {
+                    self.consume_token(Token::TFLOAT)?;
+                    Ok(DataType::Float)
+                }
+                Token::TSTRING => {
+                    self.consume_token(Token::TSTRING)?;
+                    Ok(DataType::String)
+                }
+                Token::TCHAR => {
+                    self.consume_token(Token::TCHAR)?;
+                    Ok(DataType::Char)
+                }
+                Token::IDENTIFIER(_) => {
+                    let name: String = self.consume_identifier("a type")?;
+                    Ok(DataType::Named(name))
+                }
+                token => match sized_integer_type(token) {
+                    Some(data_type) => {
+                        self.consume_token(token.clone())?;
+                        Ok(data_type)
+                    }
+                    None => Err(self.syntax_error("a type")),
+                },
             }
         }
         else {
-            panic!("no type to consume");
+            Err(self.syntax_error("a type"))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Describes an expression that can't be assigned to for an error message
+fn describe_target(expression: &ASTNode) -> String {
+    match expression.get_element() {
+        SyntaxElement::FieldAccess { field, .. } => format!("field {} of a temporary value", field),
+        _ => "an expression".to_string(),
+    }
+}
+
+/// Retrieves the binary operator a compound assignment token applies, if it is one
+fn compound_operator(token: &Token) -> Option<&'static str> {
+    match token {
+        Token::PLUSASSIGN => Some("+"),
+        Token::MINUSASSIGN => Some("-"),
+        Token::MULTIPLYASSIGN => Some("*"),
+        Token::DIVIDEASSIGN => Some("/"),
+        Token::MODASSIGN => Some("%"),
+        _ => None,
+    }
+}
+
+/// Retrieves the sized integer type a type annotation token names, if it is one
+fn sized_integer_type(token: &Token) -> Option<DataType> {
+    match token {
+        Token::TI8 => Some(DataType::I8),
+        Token::TI16 => Some(DataType::I16),
+        Token::TI32 => Some(DataType::I32),
+        Token::TI64 => Some(DataType::Integer),
+        Token::TU8 => Some(DataType::U8),
+        Token::TU16 => Some(DataType::U16),
+        Token::TU32 => Some(DataType::U32),
+        Token::TU64 => Some(DataType::U64),
+        _ => None,
+    }
+}
@@ -25,78 +25,58 @@ impl Parser {
                 function_node.add_children(function_body);
                 Ok(Some(function_node))
             },
-            _ => panic!("function")
+            _ => Err(vec![self.syntax_error("'fn'")])
         }
     }
 
     /// Parses a function declaration
     fn parse_function_declaration(&mut self) -> Result<(String, Vec<FunctionParameter>, Option<DataType>), Vec<ErrorType>> {
-        if let Some(Token::IDENTIFIER(name_chars)) = self.get_input().get(self.get_current()) {
-            self.consume_token(Token::IDENTIFIER(name_chars.clone()))?;
-            let name: String = name_chars.iter().collect();
-            self.consume_token(Token::LPAREN)?;
+        let name: String = self.consume_identifier("a function name")?;
+        self.consume_token(Token::LPAREN)?;
 
-            let mut parameters: Vec<FunctionParameter> = Vec::new();
-            while let Some(token) = self.get_input().get(self.get_current()) {
-                match token {
-                    Token::RPAREN => {
-                        self.consume_token(Token::RPAREN)?;
-                        break;
-                    },
-                    Token::IDENTIFIER(param_name_chars) => {
-                        self.consume_token(Token::IDENTIFIER(param_name_chars.clone()))?;
-                        let param_name: String = param_name_chars.iter().collect();
-    
-                        self.consume_token(Token::COLON)?;
-                        let param_type: DataType = self.consume_type()?;
-                        parameters.push(FunctionParameter::new(param_name, param_type));
-    
-                        if self.get_current() < self.get_input().len() {
-                            match self.get_input().get(self.get_current()) {
-                                Some(Token::COMMA) => self.consume_token(Token::COMMA)?,
-                                Some(Token::RPAREN) => {}
-                                _ => panic!("unexpected parse_function")
-                            }
-                        }
-                    },
-                    _ => {
-                        println!("{:?}", token);
-                        panic!("problem in function_declaration")
-                    },
-                }
-            }
-    
-            let mut return_type: Option<DataType> = None;
-                match self.get_input().get(self.get_current()) {
-                    Some(Token::COLON) => {
-                        self.consume_token(Token::COLON)?;
-                
-                        match self.consume_type() {
-                            Ok(data_type) => {
-                                return_type = Some(data_type);
-                            },
-                            _ => panic!("missing return type")
-                        }
+        let mut parameters: Vec<FunctionParameter> = Vec::new();
+        loop {
+            match self.get_input().get(self.get_current()) {
+                Some(Token::RPAREN) => {
+                    self.consume_token(Token::RPAREN)?;
+                    break;
+                },
+                Some(Token::IDENTIFIER(_)) => {
+                    let param_name: String = self.consume_identifier("a parameter name")?;
+
+                    self.consume_token(Token::COLON)?;
+                    let param_type: DataType = self.consume_type()?;
+                    parameters.push(FunctionParameter::new(param_name, param_type));
+
+                    match self.get_input().get(self.get_current()) {
+                        Some(Token::COMMA) => self.consume_token(Token::COMMA)?,
+                        Some(Token::RPAREN) => {}
+                        _ => return Err(vec![self.syntax_error("',' or ')'")]),
                     }
-                    _ => {}
-                }
-        
-            Ok((name, parameters, return_type))
-        } else {
-            panic!("functions have names silly!")
+                },
+                _ => return Err(vec![self.syntax_error("a parameter or ')'")]),
+            }
+        }
+
+        let mut return_type: Option<DataType> = None;
+        if let Some(Token::COLON) = self.get_input().get(self.get_current()) {
+            self.consume_token(Token::COLON)?;
+            // a Void annotation is the same as leaving the return type out
+            if let Some(Token::TVOID) = self.get_input().get(self.get_current()) {
+                self.consume_token(Token::TVOID)?;
+            } else {
+                return_type = Some(self.consume_type()?);
+            }
         }
+    
+        Ok((name, parameters, return_type))
     }
     
     /// Parses an enum into a name and variants
     pub fn parse_enum(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         self.consume_token(Token::ENUM)?;
     
-        let enum_name = if let Some(Token::IDENTIFIER(name_chars)) = self.get_input().get(self.get_current()) {
-            self.consume_token(Token::IDENTIFIER(name_chars.clone()))?;
-            name_chars.iter().collect()
-        } else {
-            panic!("enums have names silly!")
-        };
+        let enum_name: String = self.consume_identifier("an enum name")?;
     
         self.consume_token(Token::LBRACE)?;
     
@@ -109,10 +89,10 @@ impl Parser {
                 if let Some(Token::COMMA) = self.get_input().get(self.get_current()) {
                     self.consume_token(Token::COMMA)?;
                 } else if self.get_input().get(self.get_current()) != Some(&Token::RBRACE) {
-                    panic!("unexpected token in enum")
+                    return Err(vec![self.syntax_error("',' or ']'")]);
                 }
             } else {
-                panic!("enums have variants with names")
+                return Err(vec![self.syntax_error("a variant name")]);
             }
         }
     
@@ -125,12 +105,7 @@ impl Parser {
     pub fn parse_struct(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         self.consume_token(Token::STRUCT)?;
     
-        let struct_name = if let Some(Token::IDENTIFIER(name_chars)) = self.get_input().get(self.get_current()) {
-            self.consume_token(Token::IDENTIFIER(name_chars.clone()))?;
-            name_chars.iter().collect()
-        } else {
-            panic!("structs have names silly!")
-        };
+        let struct_name: String = self.consume_identifier("a struct name")?;
     
         self.consume_token(Token::LBRACE)?;
     
@@ -149,10 +124,10 @@ impl Parser {
                 if let Some(Token::COMMA) = self.get_input().get(self.get_current()) {
                     self.consume_token(Token::COMMA)?;
                 } else if self.get_input().get(self.get_current()) != Some(&Token::RBRACE) {
-                    panic!("unexpectd token in parse_struct")
+                    return Err(vec![self.syntax_error("',' or ']'")]);
                 }
             } else {
-                panic!("problem parse_struct")
+                return Err(vec![self.syntax_error("a field name")]);
             }
         }
     
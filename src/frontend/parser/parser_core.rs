@@ -7,48 +7,59 @@ use crate::frontend::{
         ast_struct::{ASTNode, AST}, 
         syntax_element::SyntaxElement, 
     }, 
-    lexer::token::Token, 
-    utils::error::ErrorType,
+    lexer::token::{SpannedToken, Token}, 
+    utils::{error::ErrorType, span::Span},
 };
 
 /// Parses an input of tokens into an AST   
 pub struct Parser {
     input: Vec<Token>,
+    spans: Vec<Span>,
     current: usize,
+    errors: Vec<ErrorType>, // Syntax errors that the parser has recovered from
 }
 
 impl Parser {
-    fn new(input: Vec<Token>) -> Self {
+    fn new(input: Vec<SpannedToken>) -> Self {
+        let input: Vec<SpannedToken> = drop_detached_doc_comments(input);
         Self {
-            input,
+            spans: input.iter().map(|token| token.get_span()).collect(),
+            input: input.into_iter().map(|token| token.get_token()).collect(),
             current: 0,
+            errors: Vec::new(),
         }
     } 
     
     /// Parses an input of tokens into an AST, or returns a vector of errors
     pub fn parse(input: Vec<Token>) -> Result<AST, Vec<ErrorType>> {
+        Parser::parse_with_spans(input.into_iter().map(SpannedToken::from).collect())
+    }
+
+    /// Parses an input of tokens into an AST whose nodes carry the locations of their tokens, 
+    ///     or returns a vector of errors
+    pub fn parse_with_spans(input: Vec<SpannedToken>) -> Result<AST, Vec<ErrorType>> {
         let mut parser = Parser::new(input);
         let mut root_children: Vec<ASTNode> = Vec::new();  
-        let mut errors: Vec<ErrorType> = Vec::new();
 
         while parser.get_current() < parser.get_input().len() {
+            let start: usize = parser.get_current();
             match parser.parse_router() { 
                 Ok(Some(node)) => {
                     root_children.push(node);  
                 }
                 Ok(None) => {}
                 Err(error_types) => {
-                    errors.extend(error_types);
+                    parser.recover_top_level(start, error_types);
                 }
             } 
         }
 
-        let mut root: ASTNode = ASTNode::new(SyntaxElement::TopLevelExpression);
+        let mut root: ASTNode = ASTNode::with_span(SyntaxElement::TopLevelExpression, parser.span_from(0));
         root.add_children(root_children);
-        if errors.is_empty() {
+        if parser.errors.is_empty() {
             return Ok(AST::new(root));
         }
-        Err(errors)
+        Err(parser.errors)
     }  
 
     /// Gets the current input vector
@@ -61,17 +72,108 @@ impl Parser {
         self.current.clone()
     }
 
+    /// Creates a span covering the tokens from `start` up to the most recently consumed token
+    pub fn span_from(&self, start: usize) -> Span {
+        let end: usize = self.current.min(self.spans.len());
+        if start >= end {
+            return Span::default();
+        }
+        self.spans[start].merge(&self.spans[end - 1])
+    }
+
+    /// Retrieves the location of the current token, or of the last token if there are none left
+    pub fn current_span(&self) -> Span {
+        match self.spans.get(self.current) {
+            Some(span) => *span,
+            None => self.spans.last().copied().unwrap_or_default(),
+        }
+    }
+
+    /// Creates a syntax error stating what was expected in place of the current token
+    pub fn syntax_error(&self, expected: &str) -> ErrorType {
+        let found: String = match self.input.get(self.current) {
+            Some(Token::EOF) | None => Token::EOF.to_string(),
+            Some(token) => format!("'{}'", token),
+        };
+        ErrorType::SyntaxError { 
+            expected: expected.to_string(), 
+            found, 
+            span: self.current_span(),
+        }
+    }
+
+    /// Records errors from a statement that failed to parse, then skips ahead so parsing can resume. 
+    ///     `start` is the position the statement began at, and at least one token past it is always skipped
+    pub fn recover(&mut self, start: usize, errors: Vec<ErrorType>) {
+        self.errors.extend(errors);
+        self.synchronize();
+        if self.current == start && self.current < self.input.len() {
+            self.current += 1;
+        }
+    }
+
+    /// Records errors from a top level expression that failed to parse, then skips ahead to the next 
+    ///     function, struct or enum so that the rest of the broken one isn't reported again
+    fn recover_top_level(&mut self, start: usize, errors: Vec<ErrorType>) {
+        self.errors.extend(errors);
+        if self.current == start && self.current < self.input.len() {
+            self.current += 1;
+        }
+        while let Some(token) = self.input.get(self.current) {
+            match token {
+                Token::FUNCTION | Token::STRUCT | Token::ENUM | Token::DOCCOMMENT(_) | Token::EOF => return,
+                _ => self.current += 1,
+            }
+        }
+    }
+
+    /// Skips tokens until the end of the current statement (after a ';'), the end of the current block 
+    ///     (before a '}') or the start of a new statement
+    fn synchronize(&mut self) {
+        while let Some(token) = self.input.get(self.current) {
+            match token {
+                Token::SEMICOLON => {
+                    self.current += 1;
+                    return;
+                }
+                Token::RBRACKET | Token::EOF |
+                Token::FUNCTION | Token::STRUCT | Token::ENUM | Token::DOCCOMMENT(_) |
+                Token::LET | Token::IF | Token::FOR | Token::WHILE | Token::DO | Token::MATCH |
+                Token::RETURN | Token::BREAK | Token::CONTINUE => return,
+                _ => self.current += 1,
+            }
+        }
+    }
+
     /// Consumes a token if the expected token matches the token
     pub fn consume_token(&mut self, expected_token: Token) -> Result<(), ErrorType> {
-        if let Some(token) = self.get_input().get(self.get_current()) {
-            if *token == expected_token {
+        match self.input.get(self.current) {
+            Some(token) if *token == expected_token => {
                 self.current += 1;
                 Ok(())
-            } else {
-                panic!("What is this? This is not the right token. Try again. Expected: {:?}, Actual: {:?}", expected_token, *token)
             }
-        } else {
-            panic!("You tried to consume a token that doesn't exist? Tsk tsk")
+            _ => Err(self.syntax_error(&format!("'{}'", expected_token))),
+        }
+    }
+
+    /// Consumes an identifier, returning its name
+    pub fn consume_identifier(&mut self, expected: &str) -> Result<String, ErrorType> {
+        match self.input.get(self.current) {
+            Some(Token::IDENTIFIER(name_chars)) => {
+                let name: String = name_chars.iter().collect();
+                self.current += 1;
+                Ok(name)
+            }
+            _ => Err(self.syntax_error(expected)),
+        }
+    }
+
+    /// Parses the next expression or statement, erroring if there isn't one
+    pub fn parse_required(&mut self, expected: &str) -> Result<ASTNode, Vec<ErrorType>> {
+        let error: ErrorType = self.syntax_error(expected);
+        match self.parse_router()? {
+            Some(node) => Ok(node),
+            None => Err(vec![error]),
         }
     }
     
@@ -86,8 +188,33 @@ impl Parser {
     }
 
     /// Entry point to the main parsing logic. Serves as a way to match the current token type to the file/expression we want to parse
-    // TODO Need to actually return errors here
     pub fn parse_router(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        let doc: Option<String> = self.consume_doc_comments();
+        let start: usize = self.get_current();
+        let node: Option<ASTNode> = self.route()?;
+        Ok(node.map(|mut node| {
+            node.set_span(self.span_from(start));
+            if let Some(doc) = doc {
+                node.set_doc(doc);
+            }
+            node
+        }))
+    }
+
+    /// Consumes the doc comments before a declaration, joining their lines, if there are any
+    fn consume_doc_comments(&mut self) -> Option<String> {
+        let mut lines: Vec<String> = Vec::new();
+        while let Some(Token::DOCCOMMENT(text)) = self.input.get(self.current) {
+            lines.push(text.iter().collect());
+            self.current += 1;
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        Some(lines.join("\n"))
+    }
+
+    fn route(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         if self.get_current() < self.get_input().len() {
             match self.get_input().get(self.get_current()) {
                 // top level expressions
@@ -104,29 +231,44 @@ impl Parser {
                 Some(Token::LET) => return self.parse_initialization(),
                 Some(Token::IDENTIFIER(_)) => return self.parse_identifier(),
 
-                // binary operations
-                Some(Token::PLUS) | 
+                // expressions
                 Some(Token::MINUS) | 
-                Some(Token::MULTIPLY) | 
-                Some(Token::DIVIDE) => return self.parse_binary_expression(),
-
-                // unary operations
-                Some(Token::LOGICALNOT) => return self.parse_unary_expression(), 
-
-                // base elements like primitives, and protected keywords
+                Some(Token::LOGICALNOT) | 
+                Some(Token::LPAREN) | 
                 Some(Token::INT(_)) | 
+                Some(Token::TYPEDINT(_, _)) | 
+                Some(Token::FLOAT(_)) | 
+                Some(Token::STRING(_)) | 
+                Some(Token::CHAR(_)) | 
                 Some(Token::TRUE) | 
-                Some(Token::FALSE) => return self.parse_primitive(),
+                Some(Token::FALSE) => return Ok(Some(self.parse_expression()?)),
+
+                // protected keywords
                 Some(Token::BREAK) |
                 Some(Token::RETURN) |
                 Some(Token::CONTINUE) |
                 Some(Token::SEMICOLON) |
                 Some(Token::EOF) => return self.parse_protected_keyword(),
-                _ => panic!("Are you sure this is an expression: {:?} {:?}", self.get_input().get(self.get_current()), self.get_current()),
+                _ => Err(vec![self.syntax_error("an expression or statement")]),
 
             }
         } else {
-            panic!("You hooligan. You're out of tokens")
+            Err(vec![self.syntax_error("an expression or statement")])
         }
     }
 }
+
+/// Removes doc comments that aren't directly followed by a function, struct or enum, since there is nothing 
+///     to attach them to
+fn drop_detached_doc_comments(input: Vec<SpannedToken>) -> Vec<SpannedToken> {
+    let mut attached: bool = false;
+    let mut kept: Vec<SpannedToken> = input.into_iter().rev().filter(|token| match token.get_token() {
+        Token::DOCCOMMENT(_) => attached,
+        token => {
+            attached = matches!(token, Token::FUNCTION | Token::STRUCT | Token::ENUM);
+            true
+        }
+    }).collect();
+    kept.reverse();
+    kept
+}
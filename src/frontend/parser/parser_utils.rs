@@ -6,13 +6,24 @@ use crate::frontend::{
 
 impl Parser {
     /// Converts an operator to a string representation
-    pub fn operator_to_char(&self, token: &Token) -> char {
+    pub fn operator_to_str(&self, token: &Token) -> Option<&'static str> {
         match token {
-            Token::PLUS => '+',
-            Token::MINUS => '-',
-            Token::MULTIPLY => '*',
-            Token::DIVIDE => '/',
-            _ => panic!("not an operator")
+            Token::PLUS => Some("+"),
+            Token::MINUS => Some("-"),
+            Token::MULTIPLY => Some("*"),
+            Token::DIVIDE => Some("/"),
+            Token::MOD => Some("%"),
+            Token::BITWISEXOR => Some("^"),
+            Token::EQUALEQUAL => Some("=="),
+            Token::NOTEQUAL => Some("!="),
+            Token::LESSTHAN => Some("<"),
+            Token::GREATERTHAN => Some(">"),
+            Token::LESSTHANEQUAL => Some("<="),
+            Token::GREATERTHANEQUAL => Some(">="),
+            Token::LOGICALAND => Some("&&"),
+            Token::LOGICALOR => Some("||"),
+            Token::LOGICALNOT => Some("!"),
+            _ => None
         }
     }
-}
\ No newline at end of file
+}
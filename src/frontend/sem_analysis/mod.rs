@@ -3,4 +3,6 @@ pub mod sem_analysis_core;
 
 mod sem_analysis_block; 
 mod sem_analysis_statement;
-mod sem_analysis_top_level;
\ No newline at end of file
+mod sem_analysis_top_level;
+mod sem_analysis_expression;
+mod sem_analysis_resolve;
\ No newline at end of file
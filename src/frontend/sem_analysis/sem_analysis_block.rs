@@ -3,41 +3,76 @@ use std::sync::{Arc, Mutex};
 use crate::frontend::{
     ast::ast_struct::ASTNode, 
     sem_analysis::sem_analysis_core::SemAnalysis, 
-    symbol_table::symbol_table_struct::SymbolTableStack, 
+    symbol_table::symbol_table_struct::{SymbolTable, SymbolTableStack}, 
     utils::error::ErrorType
 };
 
 impl<'a> SemAnalysis {
-    /// TODO
+    /// Completes semantic analysis of a for loop. The initializer is scoped to the loop
     pub fn sem_for_loop(&mut self, 
-        _initializer: &Option<Box<ASTNode>>, 
-        _condition: &Box<ASTNode>, 
-        _increment: &Option<Box<ASTNode>>, 
-        _body: &Box<Vec<ASTNode>>, 
-        _symbol_table: &Arc<Mutex<SymbolTableStack>>,) 
+        initializer: &Option<Box<ASTNode>>, 
+        condition: &Box<ASTNode>, 
+        increment: &Option<Box<ASTNode>>, 
+        body: &Box<Vec<ASTNode>>, 
+        symbol_table: &Arc<Mutex<SymbolTableStack>>,) 
     -> Option<Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = Vec::new();
 
-        unimplemented!("Sem analysis for loop unimplemented")
+        symbol_table.lock().unwrap().push(SymbolTable::new());
+        if let Some(initializer) = initializer {
+            errors.extend(self.sem_analysis_router(initializer, symbol_table).unwrap_or_default());
+        }
+        errors.extend(self.sem_condition(condition, symbol_table).unwrap_or_default());
+        if let Some(increment) = increment {
+            errors.extend(self.sem_analysis_router(increment, symbol_table).unwrap_or_default());
+        }
+        errors.extend(self.sem_loop_body(body, symbol_table));
+        symbol_table.lock().unwrap().pop();
+
+        if !errors.is_empty() {
+            return Some(errors);
+        }
+        None
     }
 
-    /// TODO
-    pub fn sem_while_loop(&mut self, _condition: &Box<ASTNode>, _body: &Box<Vec<ASTNode>>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis while loop unimplemented")
+    /// Completes semantic analysis of a while loop
+    pub fn sem_while_loop(&mut self, condition: &Box<ASTNode>, body: &Box<Vec<ASTNode>>, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = self.sem_condition(condition, symbol_table).unwrap_or_default();
+        errors.extend(self.sem_loop_body(body, symbol_table));
+
+        if !errors.is_empty() {
+            return Some(errors);
+        }
+        None
     }
 
-    /// TODO
-    pub fn sem_do_while_loop(&mut self, _body: &Box<Vec<ASTNode>>, _condition: &Box<ASTNode>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis do while loop unimplemented")
+    /// Completes semantic analysis of a do while loop
+    pub fn sem_do_while_loop(&mut self, body: &Box<Vec<ASTNode>>, condition: &Box<ASTNode>, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = self.sem_loop_body(body, symbol_table);
+        errors.extend(self.sem_condition(condition, symbol_table).unwrap_or_default());
+
+        if !errors.is_empty() {
+            return Some(errors);
+        }
+        None
     }
 
-    /// TODO
+    /// Completes semantic analysis of an if statement
     pub fn sem_if_statement(&mut self, 
-        _condition: &Box<ASTNode>, 
-        _then_branch: &Box<Vec<ASTNode>>, 
-        _else_branch: &Option<Box<Vec<ASTNode>>>, 
-            _symbol_table: &Arc<Mutex<SymbolTableStack>>,) 
+        condition: &Box<ASTNode>, 
+        then_branch: &Box<Vec<ASTNode>>, 
+        else_branch: &Option<Box<Vec<ASTNode>>>, 
+            symbol_table: &Arc<Mutex<SymbolTableStack>>,) 
     -> Option<Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = self.sem_condition(condition, symbol_table).unwrap_or_default();
+        errors.extend(self.sem_block(then_branch, symbol_table));
+        if let Some(else_branch) = else_branch {
+            errors.extend(self.sem_block(else_branch, symbol_table));
+        }
+
+        if !errors.is_empty() {
+            return Some(errors);
+        }
         None
     }
 }
-
@@ -10,17 +10,21 @@ use crate::frontend::{
             ASTNode, 
             ModAST, AST, ModElement, 
         }, 
+        data_type::DataType,
         syntax_element::SyntaxElement, 
-        sem_rule::RulesConfig
+        sem_rule::{RulesConfig, SemanticRule, Severity}
     },
     utils::error::ErrorType,
-    symbol_table::symbol_table_struct::SymbolTableStack,
+    symbol_table::symbol_table_struct::{SymbolTable, SymbolTableStack},
 };
 
 /// Structure for the semantic analysis phase
 pub struct SemAnalysis{
     input: ModAST,
     rules: RulesConfig,
+    current_return_type: Option<DataType>, // return type of the function being analyzed, None if void
+    loop_depth: usize, // number of loop bodies enclosing the node being analyzed
+    warnings: Vec<ErrorType>, // violations of rules configured as warnings
 }
 
 impl<'a> SemAnalysis {
@@ -28,6 +32,9 @@ impl<'a> SemAnalysis {
         Self {
             input,
             rules,
+            current_return_type: None,
+            loop_depth: 0,
+            warnings: Vec::new(),
         }
     }
 
@@ -41,6 +48,30 @@ impl<'a> SemAnalysis {
         &self.rules
     }
 
+    /// Retrieves the return type of the function being analyzed
+    pub fn get_current_return_type(&self) -> Option<DataType> {
+        self.current_return_type.clone()
+    }
+
+    /// Sets the return type of the function being analyzed
+    pub fn set_current_return_type(&mut self, return_type: Option<DataType>) {
+        self.current_return_type = return_type
+    }
+
+    /// Checks whether the node being analyzed is inside a loop body
+    pub fn in_loop(&self) -> bool {
+        self.loop_depth > 0
+    }
+
+    /// Analyzes the statements of a loop body in a new scope, returning the errors found
+    pub fn sem_loop_body(&mut self, body: &[ASTNode], symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Vec<ErrorType> {
+        self.loop_depth += 1;
+        let errors: Vec<ErrorType> = self.sem_block(body, symbol_table);
+        self.loop_depth -= 1;
+
+        errors
+    }
+
     /// Retrieves the input module for exporting
     pub fn get_output(self) -> ModAST {
         self.input
@@ -48,25 +79,38 @@ impl<'a> SemAnalysis {
 
     /// checks an ast for semantic correctness
     pub fn sem_analysis(input: ModAST, rules: RulesConfig) -> Result<ModAST, Vec<ErrorType>> { 
+        SemAnalysis::sem_analysis_with_warnings(input, rules).0
+    }
+
+    /// checks an ast for semantic correctness, also giving the violations of rules configured as warnings
+    pub fn sem_analysis_with_warnings(input: ModAST, rules: RulesConfig) -> (Result<ModAST, Vec<ErrorType>>, Vec<ErrorType>) { 
         let mut semantic_analysis: SemAnalysis = SemAnalysis::new(input, rules);
     
         let mut errors: Vec<ErrorType> = Vec::new();
     
         let elements: Vec<ModElement> = semantic_analysis.get_input().get_children().clone().into_sorted_vec();
+        let mut resolved: ModAST = ModAST::new();
     
-        for mod_element in elements {
+        for mut mod_element in elements {
             let ast: AST = mod_element.get_ast();
             let arc_mutex_symbol_table_stack: Arc<Mutex<SymbolTableStack>> = mod_element.get_sym_table_stack();
             
-            if let Some(e) = semantic_analysis.analyze_mod(ast, &arc_mutex_symbol_table_stack) {
+            if let Some(e) = semantic_analysis.analyze_mod(ast.clone(), &arc_mutex_symbol_table_stack) {
                 errors.extend(e);
+                continue;
             }
+
+            // once an element is known to be correct, its variables can be given their resolved types
+            mod_element.set_ast(semantic_analysis.resolve_ast(&ast, &arc_mutex_symbol_table_stack));
+            resolved.add_child(mod_element);
         }
     
+        let warnings: Vec<ErrorType> = std::mem::take(&mut semantic_analysis.warnings);
         if errors.is_empty() {
-            return Ok(semantic_analysis.get_output());
+            *semantic_analysis.get_input() = resolved;
+            return (Ok(semantic_analysis.get_output()), warnings);
         }
-        Err(errors)
+        (Err(errors), warnings)
     }
     
 
@@ -92,24 +136,87 @@ impl<'a> SemAnalysis {
     }
     
     
+    /// Analyzes the statements of a block in a new scope, returning the errors found
+    pub fn sem_block(&mut self, nodes: &[ASTNode], symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Vec<ErrorType> {
+        let mut errors: Vec<ErrorType> = Vec::new();
+
+        symbol_table.lock().unwrap().push(SymbolTable::new());
+        for node in nodes {
+            if let Some(e) = self.sem_analysis_router(node, symbol_table) {
+                errors.extend(e);
+            }
+        }
+        symbol_table.lock().unwrap().pop();
+
+        errors
+    }
+    
+    /// Checks a node and every expression within it against the configured rules, keeping warnings and returning 
+    ///     errors. Statements in nested blocks are checked when they are analyzed
+    fn apply_rules(&mut self, node: &ASTNode, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Vec<ErrorType> {
+        let mut errors: Vec<ErrorType> = self.apply_node_rules(node, symbol_table);
+        for expression in node.get_expressions() {
+            errors.extend(self.apply_rules(&expression, symbol_table));
+        }
+
+        errors
+    }
+
+    /// Checks a single node against the configured rules for its kind, keeping warnings and returning errors
+    fn apply_node_rules(&mut self, node: &ASTNode, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Vec<ErrorType> {
+        let rules: Vec<SemanticRule> = self.rules.get_rules(&node.get_element());
+        if rules.is_empty() {
+            return Vec::new();
+        }
+
+        let symbol_table_stack = symbol_table.lock().unwrap();
+        let mut errors: Vec<ErrorType> = Vec::new();
+        for rule in rules {
+            let Some(violation) = rule.apply_rule(node, &symbol_table_stack) else {
+                continue;
+            };
+            match rule.get_severity() {
+                Severity::Error => errors.push(violation),
+                Severity::Warning => self.warnings.push(violation),
+                Severity::Off => {},
+            }
+        }
+
+        errors
+    }
+
     /// Analyzes each node, recursively, until it has checked all nodes, and appends errors
-    fn sem_analysis_router(&mut self, node: &ASTNode, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        let mut acc_errors: Vec<ErrorType> = Vec::new();
+    pub fn sem_analysis_router(&mut self, node: &ASTNode, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = self.apply_rules(node, symbol_table);
+
+        if let Some(e) = self.sem_element(node, symbol_table) {
+            errors.extend(e);
+        }
+
+        if errors.is_empty() {
+            None
+        } else {
+            Some(errors)
+        }
+    }
 
-        let syn_errors: Option<Vec<ErrorType>> = match &node.get_element() {
+    /// Analyzes a node by its element
+    fn sem_element(&mut self, node: &ASTNode, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        // children and nested blocks are analyzed by the element that owns them, so scopes open and close around them
+        match &node.get_element() {
             SyntaxElement::NoExpression
             | SyntaxElement::ModuleExpression
             | SyntaxElement::TopLevelExpression => { None },
     
             // top level
             SyntaxElement::FunctionDeclaration { name, parameters, return_type } => {
-                self.sem_function_dec(name, parameters, return_type, symbol_table)
+                self.sem_function_dec(name, parameters, return_type, &node.get_children(), node.get_span(), symbol_table)
             },
             SyntaxElement::StructDeclaration { name, fields } => {
-                self.sem_struct_dec(name, fields, symbol_table)
+                self.sem_struct_dec(name, fields, node.get_span(), symbol_table)
             },
             SyntaxElement::EnumDeclaration { name, variants } => {
-                self.sem_enum_dec(name, variants, symbol_table)
+                self.sem_enum_dec(name, variants, node.get_span(), symbol_table)
             },
 
             // block
@@ -128,64 +235,45 @@ impl<'a> SemAnalysis {
 
             // statement
             SyntaxElement::BinaryExpression { left, operator, right } => {
-                self.sem_bin_exp(left, operator, right, symbol_table)
+                self.sem_bin_exp(left, operator, right, node.get_span(), symbol_table)
+            },
+            SyntaxElement::FieldAssignment { base, field, value } => {
+                self.sem_field_assignment(base, field, value, node.get_span(), symbol_table)
             },
             SyntaxElement::Assignment { variable, value } => {
-                self.sem_assignment(variable, value, symbol_table)
+                self.sem_assignment(variable, value, node.get_span(), symbol_table)
             },
             SyntaxElement::Initialization { variable, data_type, value } => {
-                self.sem_initialization(variable, data_type, value, symbol_table)
+                self.sem_initialization(variable, data_type, value, node.get_span(), symbol_table)
             },
             SyntaxElement::MatchStatement { to_match, arms } => {
-                self.sem_match_statement(to_match, arms, symbol_table)
+                self.sem_match_statement(to_match, arms, node.get_span(), symbol_table)
             },
             SyntaxElement::FunctionCall { name, arguments } => {
-                self.sem_function_call(name, arguments, symbol_table)
+                self.sem_function_call(name, arguments, node.get_span(), symbol_table)
             },
             SyntaxElement::UnaryExpression { operator, operand } => {
-                self.sem_unary_exp(operator, operand, symbol_table)
+                self.sem_unary_exp(operator, operand, node.get_span(), symbol_table)
             },
             SyntaxElement::Return { value } => {
-                self.sem_return(value, symbol_table)
+                self.sem_return(value, node.get_span(), symbol_table)
             },
             SyntaxElement::Break => {
-                self.sem_break(symbol_table)
+                self.sem_break(node.get_span(), symbol_table)
             },
             SyntaxElement::Continue => {
-                self.sem_continue(symbol_table)
-            },
-
-            // these might not be necessary or maybe they should be errors if hitting here? not sure
-            // SyntaxElement::Literal { data_type, value } => {
-            //     self.sem_literal(data_type, value, symbol_table)
-            // },
-            // SyntaxElement::Variable { data_type, name } => {
-            //     self.sem_variable(data_type, name, symbol_table)
-            // },
-            _ => {panic!("Should this be hit?")}
-        };
-    
-        match syn_errors {
-            Some(err) => {
-                acc_errors.extend(err)
-            }
-            _ => {}
-        }
-        // Recursively check each child and accumulate errors
-        for child in &node.get_children() {
-            let syn_errors = self.sem_analysis_router(child, symbol_table);
-            match syn_errors {
-                Some(err) => {
-                    acc_errors.extend(err)
-                }
-                _ => {}
-            }
-        }
-    
-        if !acc_errors.is_empty() {
-            Some(acc_errors.clone()) 
-        } else {
-            None
+                self.sem_continue(node.get_span(), symbol_table)
+            },
+
+            // primitives used as statements
+            SyntaxElement::Literal { .. } |
+            SyntaxElement::Variable { .. } |
+            SyntaxElement::EnumVariant { .. } |
+            SyntaxElement::Wildcard |
+            SyntaxElement::StructLiteral { .. } |
+            SyntaxElement::FieldAccess { .. } => {
+                self.sem_expression(node, symbol_table)
+            },
         }
     }
 }
\ No newline at end of file
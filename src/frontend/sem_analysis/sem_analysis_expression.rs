@@ -0,0 +1,395 @@
+use std::{collections::HashSet, sync::{Arc, Mutex}};
+
+use crate::frontend::{
+    ast::{
+        ast_struct::ASTNode, 
+        data_type::DataType, 
+        syntax_element::SyntaxElement,
+    }, 
+    sem_analysis::sem_analysis_core::SemAnalysis, 
+    symbol_table::symbol_table_struct::{SymbolTableStack, SymbolValue}, 
+    utils::{error::ErrorType, span::Span},
+};
+
+impl SemAnalysis {
+    /// Computes the type of an expression, or returns every type error found in it.
+    ///     Unknown types are accepted anywhere so that one error doesn't cascade into others
+    pub fn infer_type(&mut self, node: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Result<DataType, Vec<ErrorType>> {
+        match node.get_element() {
+            SyntaxElement::Literal { data_type, .. } => Ok(data_type),
+            SyntaxElement::Variable { name, .. } => {
+                let symbol_info = symbol_table_stack.lock().unwrap().lookup(&name);
+                match symbol_info.map(|info| info.get_data_type()) {
+                    // functions and types are declared in the same tables as variables, but have no value
+                    Some(DataType::Function | DataType::Struct | DataType::Enum) => {
+                        Err(vec![ErrorType::NotAValue { name, span: node.get_span() }])
+                    },
+                    Some(data_type) => Ok(data_type),
+                    None => Err(vec![ErrorType::UndefinedVariable { variable_name: name, span: node.get_span() }]),
+                }
+            },
+            SyntaxElement::BinaryExpression { left, operator, right } => {
+                self.infer_binary_type(&left, &operator, &right, node.get_span(), symbol_table_stack)
+            },
+            SyntaxElement::UnaryExpression { operator, operand } => {
+                self.infer_unary_type(&operator, &operand, node.get_span(), symbol_table_stack)
+            },
+            SyntaxElement::FunctionCall { name, arguments } => {
+                self.infer_call_type(&name, &arguments, node.get_span(), symbol_table_stack)
+            },
+            SyntaxElement::EnumVariant { enum_name, variant } => {
+                let symbol_info = symbol_table_stack.lock().unwrap().lookup(&enum_name);
+                match symbol_info.map(|info| info.get_value()) {
+                    Some(SymbolValue::EnumValue { variants }) if variants.contains(&variant) => Ok(DataType::Named(enum_name)),
+                    Some(SymbolValue::EnumValue { .. }) => Err(vec![ErrorType::UndefinedVariant { enum_name, variant, span: node.get_span() }]),
+                    _ => Err(vec![ErrorType::UndefinedType { type_name: enum_name, span: node.get_span() }]),
+                }
+            },
+            SyntaxElement::StructLiteral { name, fields } => {
+                self.infer_struct_literal_type(&name, &fields, node.get_span(), symbol_table_stack)
+            },
+            SyntaxElement::FieldAccess { base, field } => {
+                self.infer_field_type(&base, &field, node.get_span(), symbol_table_stack)
+            },
+            SyntaxElement::NoExpression => Ok(DataType::None),
+            // statements, declarations and the wildcard pattern aren't expressions with a type
+            SyntaxElement::ModuleExpression
+            | SyntaxElement::TopLevelExpression
+            | SyntaxElement::FunctionDeclaration { .. }
+            | SyntaxElement::StructDeclaration { .. }
+            | SyntaxElement::EnumDeclaration { .. }
+            | SyntaxElement::Initialization { .. }
+            | SyntaxElement::Assignment { .. }
+            | SyntaxElement::FieldAssignment { .. }
+            | SyntaxElement::Return { .. }
+            | SyntaxElement::IfStatement { .. }
+            | SyntaxElement::WhileLoop { .. }
+            | SyntaxElement::DoWhileLoop { .. }
+            | SyntaxElement::ForLoop { .. }
+            | SyntaxElement::MatchStatement { .. }
+            | SyntaxElement::Break
+            | SyntaxElement::Continue
+            | SyntaxElement::Wildcard => Ok(DataType::Unknown),
+        }
+    }
+
+    /// Computes the type of an expression used where a value of the expected type is required. An integer literal 
+    ///     without a suffix takes a sized expected type, as long as its value fits in that type. So does arithmetic 
+    ///     on such literals, with each literal checked against the type
+    pub fn infer_expected_type(&mut self, node: &ASTNode, expected: &DataType, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Result<DataType, Vec<ErrorType>> {
+        match (integer_literal(node), expected.integer_range()) {
+            (Some(value), Some((min, max))) if *expected != DataType::Integer => {
+                if value < min || value > max {
+                    return Err(vec![ErrorType::IntegerOverflow { data_type: expected.to_string(), span: node.get_span() }]);
+                }
+                Ok(expected.clone())
+            },
+            (None, Some(_)) if *expected != DataType::Integer && unsuffixed_integer(node) => {
+                self.infer_expected_operands(node, expected, symbol_table_stack)
+            },
+            _ => self.infer_type(node, symbol_table_stack),
+        }
+    }
+
+    /// Checks the operands of arithmetic on unsuffixed integer literals against the sized integer type the result 
+    ///     is expected to have
+    fn infer_expected_operands(&mut self, node: &ASTNode, expected: &DataType, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Result<DataType, Vec<ErrorType>> {
+        match node.get_element() {
+            SyntaxElement::BinaryExpression { left, right, .. } => {
+                let mut errors: Vec<ErrorType> = Vec::new();
+                errors.extend(self.infer_expected_type(&left, expected, symbol_table_stack).err().unwrap_or_default());
+                errors.extend(self.infer_expected_type(&right, expected, symbol_table_stack).err().unwrap_or_default());
+                if !errors.is_empty() {
+                    return Err(errors);
+                }
+            },
+            SyntaxElement::UnaryExpression { operator, operand } => {
+                self.infer_expected_type(&operand, expected, symbol_table_stack)?;
+                if expected.is_unsigned() {
+                    return Err(vec![ErrorType::UnsupportedOperator { 
+                        operator, 
+                        operand_type: expected.to_string(), 
+                        span: node.get_span(),
+                    }]);
+                }
+            },
+            _ => return self.infer_type(node, symbol_table_stack),
+        }
+        Ok(expected.clone())
+    }
+
+    /// Computes the type of a binary expression from the types of its operands. An unsuffixed integer literal operand,
+    ///     or arithmetic on such literals, takes the type of the other operand
+    pub fn infer_binary_type(&mut self, left: &ASTNode, operator: &str, right: &ASTNode, span: Span, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Result<DataType, Vec<ErrorType>> {
+        let (left_result, right_result) = if unsuffixed_integer(left) {
+            let right_result = self.infer_type(right, symbol_table_stack);
+            let left_result = match &right_result {
+                Ok(right_type) => self.infer_expected_type(left, right_type, symbol_table_stack),
+                Err(_) => self.infer_type(left, symbol_table_stack),
+            };
+            (left_result, right_result)
+        } else {
+            let left_result = self.infer_type(left, symbol_table_stack);
+            let right_result = match &left_result {
+                Ok(left_type) => self.infer_expected_type(right, left_type, symbol_table_stack),
+                Err(_) => self.infer_type(right, symbol_table_stack),
+            };
+            (left_result, right_result)
+        };
+        let (left_type, right_type) = match (left_result, right_result) {
+            (Ok(left_type), Ok(right_type)) => (left_type, right_type),
+            (left_result, right_result) => {
+                let mut errors: Vec<ErrorType> = Vec::new();
+                errors.extend(left_result.err().unwrap_or_default());
+                errors.extend(right_result.err().unwrap_or_default());
+                return Err(errors);
+            }
+        };
+
+        if left_type != right_type && left_type != DataType::Unknown && right_type != DataType::Unknown {
+            return Err(vec![ErrorType::TypeMismatch { 
+                left_type: left_type.to_string(), 
+                right_type: right_type.to_string(), 
+                span,
+            }]);
+        }
+        let operand_type: DataType = if left_type == DataType::Unknown { right_type } else { left_type };
+
+        let supported: bool = match operator {
+            "+" | "-" | "*" | "/" | "%" => operand_type.is_integer() || matches!(operand_type, DataType::Float | DataType::Unknown),
+            "^" => operand_type.is_integer() || operand_type == DataType::Unknown,
+            "<" | ">" | "<=" | ">=" => operand_type.is_integer() || matches!(operand_type, DataType::Float | DataType::Char | DataType::Unknown),
+            "==" | "!=" => match &operand_type {
+                DataType::Named(name) => is_enum(name, symbol_table_stack),
+                _ => operand_type.is_integer() || matches!(operand_type, DataType::Float | DataType::Boolean | DataType::Char | DataType::Unknown),
+            },
+            "&&" | "||" => matches!(operand_type, DataType::Boolean | DataType::Unknown),
+            _ => false,
+        };
+        if !supported {
+            return Err(vec![ErrorType::UnsupportedOperator { 
+                operator: operator.to_string(), 
+                operand_type: operand_type.to_string(), 
+                span,
+            }]);
+        }
+
+        match operator {
+            "<" | ">" | "<=" | ">=" | "==" | "!=" | "&&" | "||" => Ok(DataType::Boolean),
+            _ => Ok(operand_type),
+        }
+    }
+
+    /// Computes the type of a unary expression from the type of its operand
+    pub fn infer_unary_type(&mut self, operator: &str, operand: &ASTNode, span: Span, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Result<DataType, Vec<ErrorType>> {
+        let operand_type: DataType = self.infer_type(operand, symbol_table_stack)?;
+        let supported: bool = match operator {
+            "-" => (operand_type.is_integer() && !operand_type.is_unsigned()) || matches!(operand_type, DataType::Float | DataType::Unknown),
+            "!" => matches!(operand_type, DataType::Boolean | DataType::Unknown),
+            _ => false,
+        };
+        if !supported {
+            return Err(vec![ErrorType::UnsupportedOperator { 
+                operator: operator.to_string(), 
+                operand_type: operand_type.to_string(), 
+                span,
+            }]);
+        }
+        Ok(operand_type)
+    }
+
+    /// Checks a call against the declaration of its callee, and computes the type it returns
+    pub fn infer_call_type(&mut self, name: &str, arguments: &[ASTNode], span: Span, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Result<DataType, Vec<ErrorType>> {
+        let symbol_info = symbol_table_stack.lock().unwrap().lookup(name);
+        let (parameters, return_type) = match symbol_info.map(|info| info.get_value()) {
+            Some(SymbolValue::FunctionValue { parameters, return_type }) => (parameters, return_type),
+            Some(_) => return Err(vec![ErrorType::NotCallable { name: name.to_string(), span }]),
+            None => return Err(vec![ErrorType::UndefinedFunction { function_name: name.to_string(), span }]),
+        };
+
+        if parameters.len() != arguments.len() {
+            return Err(vec![ErrorType::ArityMismatch { 
+                function_name: name.to_string(), 
+                expected: parameters.len(), 
+                found: arguments.len(), 
+                span,
+            }]);
+        }
+
+        let mut errors: Vec<ErrorType> = Vec::new();
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            match self.infer_expected_type(argument, &parameter.get_data_type(), symbol_table_stack) {
+                Ok(argument_type) if argument_type != parameter.get_data_type() && argument_type != DataType::Unknown => {
+                    errors.push(ErrorType::TypeMismatch { 
+                        left_type: parameter.get_data_type().to_string(), 
+                        right_type: argument_type.to_string(), 
+                        span: argument.get_span(),
+                    });
+                },
+                Ok(_) => {},
+                Err(e) => errors.extend(e),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(return_type.unwrap_or(DataType::None))
+    }
+
+    /// Checks a struct literal against the declaration of its struct: every field is given once, with a value of the 
+    ///     declared type
+    pub fn infer_struct_literal_type(&mut self, name: &str, fields: &[(String, ASTNode)], span: Span, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Result<DataType, Vec<ErrorType>> {
+        let declared_fields: Vec<(String, DataType)> = struct_fields(name, symbol_table_stack)
+            .ok_or_else(|| vec![ErrorType::UndefinedType { type_name: name.to_string(), span }])?;
+
+        let mut errors: Vec<ErrorType> = Vec::new();
+        let mut given: HashSet<&String> = HashSet::new();
+        for (field, value) in fields {
+            if !given.insert(field) {
+                errors.push(ErrorType::DuplicateDefinition { name: field.to_string(), span: value.get_span() });
+                continue;
+            }
+            let field_type: DataType = match declared_fields.iter().find(|(declared, _)| declared == field) {
+                Some((_, field_type)) => field_type.clone(),
+                None => {
+                    errors.push(ErrorType::UndefinedField { struct_name: name.to_string(), field: field.to_string(), span });
+                    continue;
+                },
+            };
+            match self.infer_expected_type(value, &field_type, symbol_table_stack) {
+                Ok(value_type) if value_type != field_type && value_type != DataType::Unknown => {
+                    errors.push(ErrorType::TypeMismatch { 
+                        left_type: field_type.to_string(), 
+                        right_type: value_type.to_string(), 
+                        span: value.get_span(),
+                    });
+                },
+                Ok(_) => {},
+                Err(e) => errors.extend(e),
+            }
+        }
+        for (declared, _) in &declared_fields {
+            if !given.contains(declared) {
+                errors.push(ErrorType::MissingField { struct_name: name.to_string(), field: declared.to_string(), span });
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(DataType::Named(name.to_string()))
+    }
+
+    /// Computes the type of a field read from the declaration of the struct it is read from
+    pub fn infer_field_type(&mut self, base: &ASTNode, field: &str, span: Span, 
+            symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Result<DataType, Vec<ErrorType>> {
+        let struct_name: String = match self.infer_type(base, symbol_table_stack)? {
+            DataType::Unknown => return Ok(DataType::Unknown),
+            DataType::Named(name) if struct_fields(&name, symbol_table_stack).is_some() => name,
+            base_type => return Err(vec![ErrorType::UnsupportedOperator { 
+                operator: ".".to_string(), 
+                operand_type: base_type.to_string(), 
+                span,
+            }]),
+        };
+
+        let declared_fields: Vec<(String, DataType)> = struct_fields(&struct_name, symbol_table_stack).unwrap_or_default();
+        match declared_fields.into_iter().find(|(declared, _)| declared == field) {
+            Some((_, field_type)) => Ok(field_type),
+            None => Err(vec![ErrorType::UndefinedField { struct_name, field: field.to_string(), span }]),
+        }
+    }
+
+    /// Checks that a condition is a boolean expression
+    pub fn sem_condition(&mut self, condition: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        match self.infer_type(condition, symbol_table_stack) {
+            Ok(DataType::Boolean) | Ok(DataType::Unknown) => None,
+            Ok(data_type) => Some(vec![ErrorType::TypeMismatch { 
+                left_type: DataType::Boolean.to_string(), 
+                right_type: data_type.to_string(), 
+                span: condition.get_span(),
+            }]),
+            Err(errors) => Some(errors),
+        }
+    }
+
+    /// Checks an expression that is used as a statement, discarding its type
+    pub fn sem_expression(&mut self, node: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        self.infer_type(node, symbol_table_stack).err()
+    }
+}
+
+/// Retrieves the value of an integer literal without a sized type, which may be negated
+pub fn integer_literal(node: &ASTNode) -> Option<i128> {
+    match node.get_element() {
+        SyntaxElement::Literal { data_type: DataType::Integer, value } => value.parse::<i128>().ok(),
+        SyntaxElement::UnaryExpression { operator, operand } if operator == "-" => integer_literal(&operand).map(|value| -value),
+        _ => None,
+    }
+}
+
+/// Checks whether an expression is an integer literal without a sized type, or arithmetic on only such literals
+pub fn unsuffixed_integer(node: &ASTNode) -> bool {
+    match node.get_element() {
+        SyntaxElement::Literal { data_type: DataType::Integer, .. } => true,
+        SyntaxElement::UnaryExpression { operator, operand } => operator == "-" && unsuffixed_integer(&operand),
+        SyntaxElement::BinaryExpression { left, operator, right } => {
+            matches!(operator.as_str(), "+" | "-" | "*" | "/" | "%" | "^") && unsuffixed_integer(&left) && unsuffixed_integer(&right)
+        },
+        _ => false,
+    }
+}
+
+/// Gives the integer literals without a sized type in an expression made only of them the expected type, if it is a 
+///     sized integer type. The values are expected to have been range checked by semantic analysis
+pub fn fit_literal(node: ASTNode, expected: &DataType) -> ASTNode {
+    if !unsuffixed_integer(&node) || !expected.is_integer() {
+        return node;
+    }
+    let element: SyntaxElement = match node.get_element() {
+        SyntaxElement::Literal { value, .. } => SyntaxElement::Literal { data_type: expected.clone(), value },
+        SyntaxElement::UnaryExpression { operator, operand } => {
+            SyntaxElement::UnaryExpression { operator, operand: Box::new(fit_literal(*operand, expected)) }
+        },
+        SyntaxElement::BinaryExpression { left, operator, right } => SyntaxElement::BinaryExpression { 
+            left: Box::new(fit_literal(*left, expected)), 
+            operator, 
+            right: Box::new(fit_literal(*right, expected)),
+        },
+        element => element,
+    };
+    ASTNode::with_span(element, node.get_span())
+}
+
+/// Checks whether a name refers to a declared enum
+fn is_enum(name: &str, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> bool {
+    let symbol_info = symbol_table_stack.lock().unwrap().lookup(name);
+    matches!(symbol_info.map(|info| info.get_value()), Some(SymbolValue::EnumValue { .. }))
+}
+
+/// Retrieves the declared fields of a struct, if the name refers to one
+pub fn struct_fields(name: &str, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<(String, DataType)>> {
+    let symbol_info = symbol_table_stack.lock().unwrap().lookup(name);
+    match symbol_info.map(|info| info.get_value()) {
+        Some(SymbolValue::StructValue { fields }) => Some(fields),
+        _ => None,
+    }
+}
+
+/// Checks that a named type refers to a declared struct or enum. Built in types always exist
+pub fn check_type_exists(data_type: &DataType, span: Span, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Option<ErrorType> {
+    if let DataType::Named(name) = data_type {
+        let symbol_info = symbol_table_stack.lock().unwrap().lookup(name);
+        if !matches!(symbol_info.map(|info| info.get_value()), Some(SymbolValue::EnumValue { .. } | SymbolValue::StructValue { .. })) {
+            return Some(ErrorType::UndefinedType { type_name: name.to_string(), span });
+        }
+    }
+    None
+}
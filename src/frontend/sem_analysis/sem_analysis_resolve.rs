@@ -0,0 +1,155 @@
+/*
+Rewrites the types of variables, and of integer literals used as sized integers, once semantic analysis has accepted 
+an AST
+ */
+
+use std::sync::{Arc, Mutex};
+
+use crate::frontend::{
+    ast::{
+        ast_rewriter::ASTRewriter,
+        ast_struct::{ASTNode, AST},
+        data_type::DataType,
+        syntax_element::{MatchArm, SyntaxElement},
+    },
+    sem_analysis::{
+        sem_analysis_core::SemAnalysis,
+        sem_analysis_expression::{fit_literal, struct_fields, unsuffixed_integer},
+    },
+    symbol_table::symbol_table_struct::{SymbolInfo, SymbolTable, SymbolTableStack, SymbolValue},
+};
+
+impl SemAnalysis {
+    /// Creates a copy of an AST where every variable and initialization carries its resolved type instead of Unknown,
+    ///     and every unsuffixed integer literal carries the type it was checked against. Expects the AST to have passed 
+    ///     semantic analysis, so lookups are scoped the same way
+    pub fn resolve_ast(&mut self, ast: &AST, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> AST {
+        AST::new(self.rewrite_node(&ast.get_root(), symbol_table_stack))
+    }
+
+    /// Gives the unsuffixed integer literals of a node whose children are resolved the types they were checked against
+    fn fit_literals(&mut self, node: ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> ASTNode {
+        let element: SyntaxElement = match node.get_element() {
+            SyntaxElement::Initialization { variable, data_type, value } => {
+                let value: Box<ASTNode> = Box::new(fit_literal(*value, &data_type));
+                let data_type: DataType = match data_type {
+                    DataType::Unknown => self.infer_type(&value, symbol_table_stack).unwrap_or(DataType::Unknown),
+                    annotated => annotated,
+                };
+                symbol_table_stack.lock().unwrap().add_to_current(
+                    variable.clone(),
+                    SymbolInfo::new(data_type.clone(), SymbolValue::Node(value.clone())),
+                );
+                SyntaxElement::Initialization { variable, data_type, value }
+            },
+            SyntaxElement::BinaryExpression { left, operator, right } => {
+                // an unsuffixed integer literal, or arithmetic on them, takes the type of the other operand
+                if unsuffixed_integer(&left) {
+                    let right_type: DataType = self.infer_type(&right, symbol_table_stack).unwrap_or(DataType::Unknown);
+                    SyntaxElement::BinaryExpression { left: Box::new(fit_literal(*left, &right_type)), operator, right }
+                } else {
+                    let left_type: DataType = self.infer_type(&left, symbol_table_stack).unwrap_or(DataType::Unknown);
+                    SyntaxElement::BinaryExpression { right: Box::new(fit_literal(*right, &left_type)), operator, left }
+                }
+            },
+            SyntaxElement::Assignment { variable, value } => {
+                let variable_type: DataType = symbol_table_stack.lock().unwrap().lookup(&variable)
+                    .map_or(DataType::Unknown, |symbol_info| symbol_info.get_data_type());
+                SyntaxElement::Assignment { value: Box::new(fit_literal(*value, &variable_type)), variable }
+            },
+            SyntaxElement::Return { value } => {
+                let return_type: DataType = self.get_current_return_type().unwrap_or(DataType::None);
+                SyntaxElement::Return { value: Box::new(fit_literal(*value, &return_type)) }
+            },
+            SyntaxElement::FunctionCall { name, arguments } => {
+                let parameter_types: Vec<DataType> = match symbol_table_stack.lock().unwrap().lookup(&name).map(|info| info.get_value()) {
+                    Some(SymbolValue::FunctionValue { parameters, .. }) => parameters.iter().map(|parameter| parameter.get_data_type()).collect(),
+                    _ => Vec::new(),
+                };
+                let arguments: Vec<ASTNode> = arguments.into_iter().enumerate().map(|(index, argument)| {
+                    fit_literal(argument, parameter_types.get(index).unwrap_or(&DataType::Unknown))
+                }).collect();
+                SyntaxElement::FunctionCall { name, arguments }
+            },
+            SyntaxElement::MatchStatement { to_match, arms } => {
+                let match_type: DataType = self.infer_type(&to_match, symbol_table_stack).unwrap_or(DataType::Unknown);
+                let arms: Vec<MatchArm> = arms.into_iter()
+                    .map(|arm| MatchArm::new(fit_literal(arm.get_variant(), &match_type), arm.get_action()))
+                    .collect();
+                SyntaxElement::MatchStatement { to_match, arms }
+            },
+            SyntaxElement::StructLiteral { name, fields } => {
+                let declared_fields: Vec<(String, DataType)> = struct_fields(&name, symbol_table_stack).unwrap_or_default();
+                let fields: Vec<(String, ASTNode)> = fields.into_iter().map(|(field, value)| {
+                    let field_type: DataType = declared_fields.iter().find(|(declared, _)| *declared == field)
+                        .map_or(DataType::Unknown, |(_, field_type)| field_type.clone());
+                    (field, fit_literal(value, &field_type))
+                }).collect();
+                SyntaxElement::StructLiteral { name, fields }
+            },
+            SyntaxElement::FieldAssignment { base, field, value } => {
+                let field_type: DataType = self.infer_field_type(&base, &field, node.get_span(), symbol_table_stack)
+                    .unwrap_or(DataType::Unknown);
+                SyntaxElement::FieldAssignment { value: Box::new(fit_literal(*value, &field_type)), base, field }
+            },
+            _ => return node,
+        };
+
+        let mut resolved: ASTNode = ASTNode::with_span(element, node.get_span());
+        if let Some(doc) = node.get_doc() {
+            resolved.set_doc(doc);
+        }
+        resolved.add_children(node.get_children());
+        resolved
+    }
+}
+
+impl ASTRewriter for SemAnalysis {
+    /// Resolves the types of a node and everything beneath it
+    fn rewrite_node(&mut self, node: &ASTNode, symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> ASTNode {
+        match node.get_element() {
+            SyntaxElement::FunctionDeclaration { parameters, return_type, .. } => {
+                let mut param_table: SymbolTable = SymbolTable::new();
+                for param in &parameters {
+                    param_table.add(param.get_name(), SymbolInfo::new(param.get_data_type(), SymbolValue::StrValue(param.get_name().into())));
+                }
+                symbol_table_stack.lock().unwrap().push(param_table);
+                let enclosing_return_type: Option<DataType> = self.get_current_return_type();
+                self.set_current_return_type(return_type);
+                let resolved: ASTNode = self.rewrite_children(node, symbol_table_stack);
+                self.set_current_return_type(enclosing_return_type);
+                symbol_table_stack.lock().unwrap().pop();
+
+                resolved
+            },
+            SyntaxElement::Variable { data_type, name } => {
+                let data_type: DataType = match symbol_table_stack.lock().unwrap().lookup(&name) {
+                    Some(symbol_info) => symbol_info.get_data_type(),
+                    None => data_type,
+                };
+                ASTNode::with_span(SyntaxElement::Variable { data_type, name }, node.get_span())
+            },
+            SyntaxElement::ForLoop { .. } => {
+                // the initializer is scoped to the loop
+                symbol_table_stack.lock().unwrap().push(SymbolTable::new());
+                let resolved: ASTNode = self.rewrite_children(node, symbol_table_stack);
+                symbol_table_stack.lock().unwrap().pop();
+
+                resolved
+            },
+            _ => {
+                let resolved: ASTNode = self.rewrite_children(node, symbol_table_stack);
+                self.fit_literals(resolved, symbol_table_stack)
+            },
+        }
+    }
+
+    /// Resolves the types within a block, in a new scope
+    fn rewrite_block(&mut self, nodes: &[ASTNode], symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) -> Vec<ASTNode> {
+        symbol_table_stack.lock().unwrap().push(SymbolTable::new());
+        let resolved: Vec<ASTNode> = nodes.iter().map(|node| self.rewrite_node(node, symbol_table_stack)).collect();
+        symbol_table_stack.lock().unwrap().pop();
+
+        resolved
+    }
+}
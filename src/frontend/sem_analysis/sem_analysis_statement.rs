@@ -1,88 +1,259 @@
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::{collections::HashSet, sync::{Arc, Mutex}};
 
 use crate::frontend::{
     ast::{
         ast_struct::ASTNode, 
         data_type::DataType, 
-        syntax_element::MatchArm
+        syntax_element::{MatchArm, SyntaxElement},
     }, 
-    sem_analysis::sem_analysis_core::SemAnalysis, 
-    symbol_table::{
-        symbol_table_struct::SymbolTableStack, 
-        symbol_table_struct::SymbolTable,
-    }, 
-    utils::error::ErrorType,
+    sem_analysis::{sem_analysis_core::SemAnalysis, sem_analysis_expression::check_type_exists}, 
+    symbol_table::symbol_table_struct::{SymbolInfo, SymbolTableStack, SymbolValue}, 
+    utils::{error::ErrorType, span::Span},
 };
 
 impl<'a> SemAnalysis {
     /// Completes semantic analysis of variable assignment
     pub fn sem_assignment(&mut self, 
         variable: &String, 
-        _value: &Box<ASTNode>, 
+        value: &Box<ASTNode>, 
+        span: Span,
+        symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) 
+    -> Option<Vec<ErrorType>> {
+
+        let symbol_info: Option<SymbolInfo> = symbol_table_stack.lock().unwrap().lookup(variable);
+        let variable_type: DataType = match symbol_info {
+            Some(symbol_info) => symbol_info.get_data_type(),
+            None => return Some(vec![ErrorType::UndefinedVariable { variable_name: variable.to_string(), span }]),
+        };
+        if matches!(variable_type, DataType::Function | DataType::Struct | DataType::Enum) {
+            return Some(vec![ErrorType::InvalidAssignment { target: variable.to_string(), span }]);
+        }
+
+        match self.infer_expected_type(value, &variable_type, symbol_table_stack) {
+            Ok(DataType::None) => Some(vec![ErrorType::VoidValue { span }]),
+            Ok(value_type) if !types_compatible(&variable_type, &value_type) => {
+                Some(vec![ErrorType::TypeMismatch { 
+                    left_type: variable_type.to_string(), 
+                    right_type: value_type.to_string(), 
+                    span,
+                }])
+            },
+            Ok(_) => None,
+            Err(errors) => Some(errors),
+        }
+    }
+
+    /// Completes semantic analysis of an assignment to a struct field, which must be declared and match the value's type
+    pub fn sem_field_assignment(&mut self, 
+        base: &ASTNode, 
+        field: &str, 
+        value: &ASTNode, 
+        span: Span,
         symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) 
     -> Option<Vec<ErrorType>> {
 
+        if !base.is_assignable() {
+            return Some(vec![ErrorType::InvalidAssignment { target: format!("field {} of a temporary value", field), span }]);
+        }
+        let field_type: DataType = match self.infer_field_type(base, field, span, symbol_table_stack) {
+            Ok(field_type) => field_type,
+            Err(errors) => return Some(errors),
+        };
+
+        match self.infer_expected_type(value, &field_type, symbol_table_stack) {
+            Ok(DataType::None) => Some(vec![ErrorType::VoidValue { span }]),
+            Ok(value_type) if !types_compatible(&field_type, &value_type) => {
+                Some(vec![ErrorType::TypeMismatch { 
+                    left_type: field_type.to_string(), 
+                    right_type: value_type.to_string(), 
+                    span,
+                }])
+            },
+            Ok(_) => None,
+            Err(errors) => Some(errors),
+        }
+    }
+
+    /// Completes semantic analysis of a binary expression used as a statement
+    pub fn sem_bin_exp(&mut self, left: &Box<ASTNode>, operator: &str, right: &Box<ASTNode>, span: Span, 
+            symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        self.infer_binary_type(left, operator, right, span, symbol_table).err()
+    }
+
+    /// Completes semantic analysis of a variable initialization, checking the value against the annotated type
+    ///     and adding the variable to the current scope. Without an annotation, the variable takes the value's type
+    pub fn sem_initialization(&mut self, variable: &String, data_type: &DataType, value: &Box<ASTNode>, span: Span, 
+            symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = Vec::new();
+
+        // an undeclared annotation is reported once, then treated as unknown so it doesn't cascade
+        let data_type: DataType = match check_type_exists(data_type, span, symbol_table) {
+            Some(error) => {
+                errors.push(error);
+                DataType::Unknown
+            },
+            None => data_type.clone(),
+        };
+        let mut variable_type: DataType = data_type.clone();
+
+        match self.infer_expected_type(value, &data_type, symbol_table) {
+            Ok(DataType::None) => errors.push(ErrorType::VoidValue { span }),
+            Ok(value_type) if !types_compatible(&data_type, &value_type) => {
+                errors.push(ErrorType::TypeMismatch { 
+                    left_type: data_type.to_string(), 
+                    right_type: value_type.to_string(), 
+                    span,
+                });
+            },
+            Ok(value_type) if data_type == DataType::Unknown => variable_type = value_type,
+            Ok(_) => {},
+            Err(e) => errors.extend(e),
+        }
+
+        symbol_table.lock().unwrap().add_to_current(
+            variable.to_string(), 
+            SymbolInfo::new(variable_type, SymbolValue::Node(value.clone())),
+        );
+
+        if !errors.is_empty() {
+            return Some(errors);
+        }
+        None
+    }
+
+    /// Completes semantic analysis of a match statement, checking each arm's pattern against the matched value. 
+    ///     Arms whose pattern is already covered are unreachable, and a match on an enum has to cover every variant
+    pub fn sem_match_statement(&mut self, to_match: &Box<ASTNode>, arms: &Vec<MatchArm>, span: Span, 
+            symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
         let mut errors: Vec<ErrorType> = Vec::new();
-        let stack: MutexGuard<'_, SymbolTableStack> = symbol_table_stack.lock().unwrap();
-        
-        let mut var_found: bool = false;
 
-        for symbol_table_arc in stack.get_elements().iter().rev() {
-            let sym_table: MutexGuard<'_, SymbolTable> = symbol_table_arc.lock().unwrap();
+        let match_type: DataType = match self.infer_type(to_match, symbol_table) {
+            Ok(match_type) => match_type,
+            Err(e) => {
+                errors.extend(e);
+                DataType::Unknown
+            }
+        };
+        let variants: Option<Vec<String>> = match &match_type {
+            DataType::Named(name) => match symbol_table.lock().unwrap().lookup(name).map(|info| info.get_value()) {
+                Some(SymbolValue::EnumValue { variants }) => Some(variants),
+                _ => None,
+            },
+            _ => None,
+        };
+        if !(match_type.is_integer() || matches!(match_type, DataType::Boolean | DataType::Unknown)) && variants.is_none() {
+            errors.push(ErrorType::UnsupportedOperator { 
+                operator: "match".to_string(), 
+                operand_type: match_type.to_string(), 
+                span: to_match.get_span(),
+            });
+        }
 
-            if sym_table.get(variable).is_some() {
-                var_found = true;
-                break;
+        let mut covered: HashSet<String> = HashSet::new();
+        let mut has_wildcard: bool = false;
+        for arm in arms {
+            let variant: ASTNode = arm.get_variant();
+            if has_wildcard {
+                errors.push(ErrorType::UnreachableArm { span: variant.get_span() });
+            } else if arm.is_wildcard() {
+                has_wildcard = true;
+            } else {
+                match pattern_key(&variant) {
+                    Some(key) => if !covered.insert(key) {
+                        errors.push(ErrorType::UnreachableArm { span: variant.get_span() });
+                    },
+                    None => errors.push(ErrorType::InvalidPattern { span: variant.get_span() }),
+                }
+                match self.infer_expected_type(&variant, &match_type, symbol_table) {
+                    Ok(variant_type) if !types_compatible(&match_type, &variant_type) => {
+                        errors.push(ErrorType::TypeMismatch { 
+                            left_type: match_type.to_string(), 
+                            right_type: variant_type.to_string(), 
+                            span: variant.get_span(),
+                        });
+                    },
+                    Ok(_) => {},
+                    Err(e) => errors.extend(e),
+                }
             }
+            errors.extend(self.sem_block(&arm.get_action(), symbol_table));
         }
 
-        if !var_found {
-            errors.push(ErrorType::DevError{})
+        if let (Some(variants), false) = (variants, has_wildcard) {
+            let missing: Vec<String> = variants.into_iter().filter(|variant| !covered.contains(variant)).collect();
+            if !missing.is_empty() {
+                errors.push(ErrorType::NonExhaustiveMatch { missing, span });
+            }
         }
+
         if !errors.is_empty() {
             return Some(errors);
         }
         None
     }
 
-    /// TODO
-    pub fn sem_bin_exp(&mut self, _left: &Box<ASTNode>, _operator: &String, _right: &Box<ASTNode>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis bin exp unimplemented")
+    /// Completes semantic analysis of a function call used as a statement
+    pub fn sem_function_call(&mut self, name: &str, arguments: &[ASTNode], span: Span, 
+            symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        self.infer_call_type(name, arguments, span, symbol_table).err()
     }
 
-    /// TODO
-    pub fn sem_initialization(&mut self, _variable: &String, _data_type: &DataType, _value: &Box<ASTNode>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis init unimplemented")
+    /// Completes semantic analysis of a unary expression used as a statement
+    pub fn sem_unary_exp(&mut self, operator: &str, operand: &Box<ASTNode>, span: Span, 
+            symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        self.infer_unary_type(operator, operand, span, symbol_table).err()
     }
 
-    /// TODO
-    pub fn sem_match_statement(&mut self, _to_match: &Box<ASTNode>, _arms: &Vec<MatchArm>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis match unimplemented")
-    }
+    /// Completes semantic analysis of a return statement, checking the value against the function's return type
+    pub fn sem_return(&mut self, value: &ASTNode, span: Span, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        let return_type: DataType = self.get_current_return_type().unwrap_or(DataType::None);
+        let value_type: DataType = match self.infer_expected_type(value, &return_type, symbol_table) {
+            Ok(value_type) => value_type,
+            Err(errors) => return Some(errors),
+        };
 
-    /// TODO
-    pub fn sem_function_call(&mut self, _name: &String, _arguments: &Vec<ASTNode>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis fn call unimplemented")
+        if !types_compatible(&return_type, &value_type) {
+            return Some(vec![ErrorType::TypeMismatch { 
+                left_type: return_type.to_string(), 
+                right_type: value_type.to_string(), 
+                span,
+            }]);
+        }
+        None
     }
 
-    /// TODO
-    pub fn sem_unary_exp(&mut self, _operator: &String, _operand: &Box<ASTNode>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis unary op unimplemented")
+    /// Completes semantic analysis of a break statement, which must be inside a loop body
+    pub fn sem_break(&mut self, span: Span, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        if !self.in_loop() {
+            return Some(vec![ErrorType::OutsideLoop { statement: "break".to_string(), span }]);
+        }
+        None
     }
 
-    /// TODO
-    pub fn sem_return(&mut self, _value: &Box<ASTNode>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis return unimplemented")
+    /// Completes semantic analysis of a continue statement, which must be inside a loop body
+    pub fn sem_continue(&mut self, span: Span, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        if !self.in_loop() {
+            return Some(vec![ErrorType::OutsideLoop { statement: "continue".to_string(), span }]);
+        }
+        None
     }
 
-    /// TODO
-    pub fn sem_break(&mut self, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis break unimplemented")
-    }
+}
 
-    /// TODO
-    pub fn sem_continue(&mut self, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis continue unimplemented")
+/// Identifies the value a match pattern stands for, so repeated patterns can be found. Only literals, negated integer 
+///     literals and enum variants are patterns
+fn pattern_key(pattern: &ASTNode) -> Option<String> {
+    match pattern.get_element() {
+        SyntaxElement::Literal { data_type, value } if data_type.is_integer() || data_type == DataType::Boolean => Some(value),
+        SyntaxElement::UnaryExpression { operator, operand } if operator == "-" => {
+            pattern_key(&operand).map(|value| format!("-{}", value))
+        },
+        SyntaxElement::EnumVariant { variant, .. } => Some(variant),
+        _ => None,
     }
+}
 
+/// Whether a value of type `found` can be used where `expected` is required
+fn types_compatible(expected: &DataType, found: &DataType) -> bool {
+    expected == found || *expected == DataType::Unknown || *found == DataType::Unknown
 }
\ No newline at end of file
@@ -2,32 +2,38 @@ use std::{collections::HashSet, sync::{Arc, Mutex, MutexGuard}};
 
 use crate::frontend::{
     ast::{
+        ast_struct::ASTNode,
         data_type::DataType, 
         syntax_element::FunctionParameter
     }, 
-    sem_analysis::sem_analysis_core::SemAnalysis, 
-    symbol_table::symbol_table_struct::SymbolTableStack, 
-    utils::error::ErrorType
+    sem_analysis::{sem_analysis_core::SemAnalysis, sem_analysis_expression::check_type_exists}, 
+    symbol_table::symbol_table_struct::{SymbolInfo, SymbolTable, SymbolTableStack, SymbolValue}, 
+    utils::{error::ErrorType, span::Span},
 };
 
 impl<'a> SemAnalysis {
-    /// Completes semantic analysis on a function declaration
+    /// Completes semantic analysis on a function declaration, then analyzes its body with the parameters in scope
+    ///     and checks that it returns on every path
     pub fn sem_function_dec(&mut self, 
         name: &String, 
         parameters: &Vec<FunctionParameter>, 
         return_type: &Option<DataType>, 
+        body: &[ASTNode],
+        span: Span,
         symbol_table_stack: &Arc<Mutex<SymbolTableStack>>) 
     -> Option<Vec<ErrorType>> {
 
         let mut errors: Vec<ErrorType> = Vec::new();
 
-        let stack: MutexGuard<'_, SymbolTableStack> = symbol_table_stack.lock().unwrap();
+        {
+            let stack: MutexGuard<'_, SymbolTableStack> = symbol_table_stack.lock().unwrap();
 
-        // deny functional polymorphism
-        if let Some(current_symbol_table) = stack.peek() {
-            let table = current_symbol_table.lock().unwrap();
-            if table.get(name).is_some() {
-                errors.push(ErrorType::DevError{})
+            // deny functional polymorphism
+            if let Some(current_symbol_table) = stack.peek() {
+                let table = current_symbol_table.lock().unwrap();
+                if table.get(name).is_some() {
+                    errors.push(ErrorType::DevError{})
+                }
             }
         }
 
@@ -49,19 +55,73 @@ impl<'a> SemAnalysis {
             }
         }
 
+        // deny named types that were never declared
+        for param in parameters {
+            errors.extend(check_type_exists(&param.get_data_type(), span, symbol_table_stack));
+        }
+        if let Some(return_type) = return_type {
+            errors.extend(check_type_exists(return_type, span, symbol_table_stack));
+        }
+
+        // parameters get their own scope around the body
+        let mut param_table: SymbolTable = SymbolTable::new();
+        for param in parameters {
+            param_table.add(param.get_name(), SymbolInfo::new(param.get_data_type(), SymbolValue::StrValue(param.get_name().into())));
+        }
+        symbol_table_stack.lock().unwrap().push(param_table);
+        let enclosing_return_type: Option<DataType> = self.get_current_return_type();
+        self.set_current_return_type(return_type.clone());
+
+        errors.extend(self.sem_block(body, symbol_table_stack));
+
+        self.set_current_return_type(enclosing_return_type);
+        symbol_table_stack.lock().unwrap().pop();
+
+        // void functions may fall off the end of their body, others must return on every path
+        if return_type.is_some() && !body.iter().any(ASTNode::always_returns) {
+            errors.push(ErrorType::MissingReturn { function_name: name.to_string(), span });
+        }
+
         if !errors.is_empty() {
             return Some(errors);
         }
         None
     }
     
-    /// TODO
-    pub fn sem_struct_dec(&mut self, _name: &String, _fields: &Vec<(String, DataType)>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis of structs unimplemented")
+    /// Completes semantic analysis on a struct declaration, denying repeated fields and fields of undeclared types
+    pub fn sem_struct_dec(&mut self, _name: &String, fields: &Vec<(String, DataType)>, span: Span, symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = Vec::new();
+
+        let mut field_names: HashSet<&String> = HashSet::new();
+        for (field, data_type) in fields {
+            if !field_names.insert(field) {
+                errors.push(ErrorType::DuplicateDefinition { name: field.to_string(), span });
+            }
+            if let Some(e) = check_type_exists(data_type, span, symbol_table) {
+                errors.push(e);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Some(errors);
+        }
+        None
     }
 
-    /// TODO
-    pub fn sem_enum_dec(&mut self, _name: &String, _variants: &Vec<String>, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
-        unimplemented!("Sem analysis of enums unimplemented")
+    /// Completes semantic analysis on an enum declaration, denying repeated variants
+    pub fn sem_enum_dec(&mut self, _name: &String, variants: &Vec<String>, span: Span, _symbol_table: &Arc<Mutex<SymbolTableStack>>) -> Option<Vec<ErrorType>> {
+        let mut errors: Vec<ErrorType> = Vec::new();
+
+        let mut variant_names: HashSet<&String> = HashSet::new();
+        for variant in variants {
+            if !variant_names.insert(variant) {
+                errors.push(ErrorType::DuplicateDefinition { name: variant.to_string(), span });
+            }
+        }
+
+        if !errors.is_empty() {
+            return Some(errors);
+        }
+        None
     }
-}
\ No newline at end of file
+}
@@ -83,6 +83,11 @@ impl SymbolTable {
     pub fn get(&self, name: &str) -> Option<&SymbolInfo> {
         self.values.get(name)
     }
+
+    /// Retrieves all the values in the symbol table
+    pub fn get_values(&self) -> &HashMap<String, SymbolInfo> {
+        &self.values
+    }
 }
 
 /// A stack of symbol tables, used to represent different levels of scope
@@ -125,6 +130,20 @@ impl SymbolTableStack {
         self.elements.len()
     }
 
+    /// Retrieves a symbol from the innermost table that defines it, else None
+    pub fn lookup(&self, name: &str) -> Option<SymbolInfo> {
+        self.elements.iter().rev()
+            .find_map(|table| table.lock().unwrap().get(name).cloned())
+    }
+
+    /// Adds a symbol to the topmost table on the stack
+    pub fn add_to_current(&mut self, name: String, info: SymbolInfo) {
+        match self.peek() {
+            Some(table) => table.lock().unwrap().add(name, info),
+            None => panic!("No symbol table on the stack."),
+        }
+    }
+
     /// Retreives all the tables off the stack
     pub fn get_elements(&self) -> &Vec<Arc<Mutex<SymbolTable>>> {
         &self.elements
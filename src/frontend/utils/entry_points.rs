@@ -2,7 +2,8 @@ use std::fs::File;
 use std::io::{BufReader, BufRead};
 use std::path::Path;
 
-/// Locates the positions of top level expressions in a file
+/// Locates the positions of top level expressions in a file. Lines inside block comments are ignored, and a 
+///     top level expression starts at the doc comments written above it
 pub fn entry_points(path: &Path) -> Vec<usize> {
     let file: Result<File, std::io::Error> = File::open(path);
 
@@ -10,16 +11,25 @@ pub fn entry_points(path: &Path) -> Vec<usize> {
         Ok(file) => {
             let reader: BufReader<File> = BufReader::new(file);
             let mut lines: Vec<usize> = Vec::new();
+            let mut comment_depth: usize = 0;
+            let mut doc_start: Option<usize> = None;
 
             for (index, line) in reader.lines().enumerate() {
                 match line {
                     Ok(line) => {
-                        let line: &str = line.trim_start();
-                        if line.starts_with("import") || line.starts_with("pub") ||
-                        line.starts_with("fn") || line.starts_with("struct") ||
-                        line.starts_with("enum") {
-                            lines.push(index)
+                        let trimmed: &str = line.trim_start();
+                        if comment_depth == 0 {
+                            if trimmed.starts_with("///") && !trimmed.starts_with("////") {
+                                doc_start.get_or_insert(index);
+                            } else if trimmed.starts_with("import") || trimmed.starts_with("pub") ||
+                            trimmed.starts_with("fn") || trimmed.starts_with("struct") ||
+                            trimmed.starts_with("enum") {
+                                lines.push(doc_start.take().unwrap_or(index))
+                            } else if !trimmed.is_empty() {
+                                doc_start = None;
+                            }
                         }
+                        comment_depth = block_comment_depth(&line, comment_depth);
                     }
                     Err(e) => {panic!("{:?}", e)}
                 }
@@ -28,4 +38,34 @@ pub fn entry_points(path: &Path) -> Vec<usize> {
         }
         _ => panic!("File not found")
     }
-}
\ No newline at end of file
+}
+
+/// Computes how many block comments are open at the end of a line, given how many were open at its start. 
+///     Line comments and string or char literals outside of block comments are skipped
+fn block_comment_depth(line: &str, mut depth: usize) -> usize {
+    let mut chars = line.chars().peekable();
+    while let Some(current) = chars.next() {
+        match (current, chars.peek()) {
+            ('/', Some('*')) => {
+                chars.next();
+                depth += 1;
+            },
+            ('*', Some('/')) if depth > 0 => {
+                chars.next();
+                depth -= 1;
+            },
+            ('/', Some('/')) if depth == 0 => break,
+            ('"', _) | ('\'', _) if depth == 0 => {
+                while let Some(quoted) = chars.next() {
+                    if quoted == '\\' {
+                        chars.next();
+                    } else if quoted == current {
+                        break;
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+    depth
+}
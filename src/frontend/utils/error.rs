@@ -1,6 +1,10 @@
+use std::fmt;
+
+use crate::frontend::utils::span::Span;
+
 /// Errors in the compilation process
 #[derive(Debug, PartialEq, Clone)]
-pub enum ErrorType {    
+pub enum ErrorType {
     /// Binary operation has incompatible types
     TypeMismatch {
         /// Left type in comparison
@@ -8,12 +12,18 @@ pub enum ErrorType {
 
         /// Right type in comparison
         right_type: String,
+
+        /// Location of the error
+        span: Span,
     },
 
     /// Variable used but not declared
     UndefinedVariable {
         /// The requested variable's name
         variable_name: String,
+
+        /// Location of the error
+        span: Span,
     },
 
     /// Unsupported operator on types given
@@ -22,30 +32,362 @@ pub enum ErrorType {
         operator: String,
         /// Type of operand in operation
         operand_type: String,
+
+        /// Location of the error
+        span: Span,
     },
 
     /// Divisor is zero
     DivisionByZero {
         /// Attempted operation
         operation: String,
+
+        /// Location of the error
+        span: Span,
     },
 
-    /// Invalid assignment to a target 
+    /// Constant expression gives an integer too large or small for its type
+    IntegerOverflow {
+        /// The integer type
+        data_type: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Invalid assignment to a target
     InvalidAssignment {
         /// The name of the target variable
         target: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Call of a Void function used where a value is needed
+    VoidValue {
+        /// Location of the error
+        span: Span,
     },
 
     /// Unrecognized token
     UnrecognizedToken {
         /// The unrecognized token
         token: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// String or char literal not closed before the end of its line
+    UnterminatedLiteral {
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Block comment not closed before the end of the input
+    UnterminatedComment {
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Escape sequence in a string or char literal is not recognized
+    InvalidEscape {
+        /// The offending escape sequence
+        sequence: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Number literal with digits that don't belong to its base, an unknown suffix or a value too large for its type
+    InvalidLiteral {
+        /// The offending literal
+        literal: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Input does not follow the grammar of the language
+    SyntaxError {
+        /// What the parser expected to find
+        expected: String,
+
+        /// What the parser found instead
+        found: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Function called but not declared
+    UndefinedFunction {
+        /// The requested function's name
+        function_name: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Function called with the wrong number of arguments
+    ArityMismatch {
+        /// The called function's name
+        function_name: String,
+
+        /// Number of parameters the function declares
+        expected: usize,
+
+        /// Number of arguments given
+        found: usize,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Call of a symbol that is not a function
+    NotCallable {
+        /// The called symbol's name
+        name: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Symbol that is not a value, such as a function or a type, used as one
+    NotAValue {
+        /// The symbol's name
+        name: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Function with a return type has a path that does not return a value
+    MissingReturn {
+        /// The function's name
+        function_name: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Break or continue statement used outside of a loop body
+    OutsideLoop {
+        /// The offending statement, break or continue
+        statement: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Type used but not declared
+    UndefinedType {
+        /// The requested type's name
+        type_name: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Enum variant used but not declared by its enum
+    UndefinedVariant {
+        /// Name of the enum
+        enum_name: String,
+
+        /// The requested variant's name
+        variant: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Name declared more than once in the same declaration
+    DuplicateDefinition {
+        /// The repeated name
+        name: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Struct field used but not declared by its struct
+    UndefinedField {
+        /// Name of the struct
+        struct_name: String,
+
+        /// The requested field's name
+        field: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Struct literal does not give a value for a declared field
+    MissingField {
+        /// Name of the struct
+        struct_name: String,
+
+        /// The missing field's name
+        field: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Match arm pattern is not a literal, an enum variant or '_'
+    InvalidPattern {
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Match arm can never be reached because earlier arms cover its pattern
+    UnreachableArm {
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Match on an enum does not cover every variant
+    NonExhaustiveMatch {
+        /// Variants without an arm
+        missing: Vec<String>,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Violation of a configured semantic rule
+    RuleViolation {
+        /// Name of the rule
+        rule: String,
+
+        /// What the rule found
+        message: String,
+
+        /// Location of the error
+        span: Span,
+    },
+
+    /// Line of a rules configuration file that can't be read
+    InvalidConfig {
+        /// Line of the configuration file, 1-based
+        line: usize,
+
+        /// What is wrong with the line
+        message: String,
     },
 
     /// Stand-in errors that need to be updated for better error handling
     DevError {},
 }
 
+impl ErrorType {
+    /// Retrieves the location of the error, if it has one
+    pub fn get_span(&self) -> Option<Span> {
+        match self {
+            ErrorType::TypeMismatch { span, .. }
+            | ErrorType::UndefinedVariable { span, .. }
+            | ErrorType::UnsupportedOperator { span, .. }
+            | ErrorType::DivisionByZero { span, .. }
+            | ErrorType::IntegerOverflow { span, .. }
+            | ErrorType::InvalidAssignment { span, .. }
+            | ErrorType::VoidValue { span }
+            | ErrorType::UnrecognizedToken { span, .. }
+            | ErrorType::UnterminatedLiteral { span }
+            | ErrorType::UnterminatedComment { span }
+            | ErrorType::InvalidEscape { span, .. }
+            | ErrorType::InvalidLiteral { span, .. }
+            | ErrorType::SyntaxError { span, .. }
+            | ErrorType::UndefinedFunction { span, .. }
+            | ErrorType::ArityMismatch { span, .. }
+            | ErrorType::NotCallable { span, .. }
+            | ErrorType::NotAValue { span, .. }
+            | ErrorType::MissingReturn { span, .. }
+            | ErrorType::OutsideLoop { span, .. }
+            | ErrorType::UndefinedType { span, .. }
+            | ErrorType::UndefinedVariant { span, .. }
+            | ErrorType::DuplicateDefinition { span, .. }
+            | ErrorType::UndefinedField { span, .. }
+            | ErrorType::MissingField { span, .. }
+            | ErrorType::InvalidPattern { span }
+            | ErrorType::UnreachableArm { span }
+            | ErrorType::NonExhaustiveMatch { span, .. }
+            | ErrorType::RuleViolation { span, .. } => Some(*span),
+            ErrorType::InvalidConfig { .. } | ErrorType::DevError {} => None,
+        }
+    }
+}
+
+impl fmt::Display for ErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(span) = self.get_span() {
+            write!(f, "{}: ", span)?;
+        }
+        match self {
+            ErrorType::TypeMismatch { left_type, right_type, .. } =>
+                write!(f, "type mismatch between {} and {}", left_type, right_type),
+            ErrorType::UndefinedVariable { variable_name, .. } =>
+                write!(f, "undefined variable {}", variable_name),
+            ErrorType::UnsupportedOperator { operator, operand_type, .. } =>
+                write!(f, "operator {} is not supported on {}", operator, operand_type),
+            ErrorType::DivisionByZero { operation, .. } =>
+                write!(f, "division by zero in {}", operation),
+            ErrorType::IntegerOverflow { data_type, .. } =>
+                write!(f, "constant expression overflows {}", data_type),
+            ErrorType::InvalidAssignment { target, .. } =>
+                write!(f, "invalid assignment to {}", target),
+            ErrorType::VoidValue { .. } =>
+                write!(f, "a Void call has no value to use"),
+            ErrorType::UnrecognizedToken { token, .. } =>
+                write!(f, "unrecognized token {}", token),
+            ErrorType::UnterminatedLiteral { .. } =>
+                write!(f, "unterminated literal"),
+            ErrorType::UnterminatedComment { .. } =>
+                write!(f, "unterminated block comment"),
+            ErrorType::InvalidEscape { sequence, .. } =>
+                write!(f, "invalid escape sequence {}", sequence),
+            ErrorType::InvalidLiteral { literal, .. } =>
+                write!(f, "invalid number literal {}", literal),
+            ErrorType::SyntaxError { expected, found, .. } =>
+                write!(f, "expected {}, found {}", expected, found),
+            ErrorType::UndefinedFunction { function_name, .. } =>
+                write!(f, "undefined function {}", function_name),
+            ErrorType::ArityMismatch { function_name, expected, found, .. } =>
+                write!(f, "function {} expects {} arguments but {} were given", function_name, expected, found),
+            ErrorType::NotCallable { name, .. } =>
+                write!(f, "{} is not a function", name),
+            ErrorType::NotAValue { name, .. } =>
+                write!(f, "{} is not a value", name),
+            ErrorType::MissingReturn { function_name, .. } =>
+                write!(f, "function {} does not return a value on every path", function_name),
+            ErrorType::OutsideLoop { statement, .. } =>
+                write!(f, "{} used outside of a loop", statement),
+            ErrorType::UndefinedType { type_name, .. } =>
+                write!(f, "undefined type {}", type_name),
+            ErrorType::UndefinedVariant { enum_name, variant, .. } =>
+                write!(f, "enum {} has no variant {}", enum_name, variant),
+            ErrorType::DuplicateDefinition { name, .. } =>
+                write!(f, "{} is defined more than once", name),
+            ErrorType::UndefinedField { struct_name, field, .. } =>
+                write!(f, "struct {} has no field {}", struct_name, field),
+            ErrorType::MissingField { struct_name, field, .. } =>
+                write!(f, "struct {} is missing field {}", struct_name, field),
+            ErrorType::InvalidPattern { .. } =>
+                write!(f, "match patterns must be literals, enum variants or '_'"),
+            ErrorType::UnreachableArm { .. } =>
+                write!(f, "match arm is unreachable"),
+            ErrorType::NonExhaustiveMatch { missing, .. } =>
+                write!(f, "match does not cover {}", missing.join(", ")),
+            ErrorType::RuleViolation { rule, message, .. } =>
+                write!(f, "{} [{}]", message, rule),
+            ErrorType::InvalidConfig { line, message } =>
+                write!(f, "{}: {}", line, message),
+            ErrorType::DevError {} =>
+                write!(f, "unspecified error"),
+        }
+    }
+}
+
 impl From<ErrorType> for Vec<ErrorType> {
     fn from(err: ErrorType) -> Self {
         vec![err]
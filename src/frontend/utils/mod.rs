@@ -5,4 +5,7 @@ pub mod error;
 pub mod entry_points;
 
 /// Sets up a Thread Pool for Execution
-pub mod thread_pool_executor;
\ No newline at end of file
+pub mod thread_pool_executor;
+
+/// Source Locations
+pub mod span;
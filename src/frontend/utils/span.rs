@@ -0,0 +1,65 @@
+use std::fmt;
+
+/// Location of a piece of source code. Offsets are in bytes, line and column are 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    start: usize,
+    end: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Span {
+    /// Creates a new span from a byte range and the line/column of its first character
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+
+    /// Retrieves the byte offset of the start of the span
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    /// Retrieves the byte offset one past the end of the span
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+
+    /// Retrieves the line the span starts on
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    /// Retrieves the column the span starts on
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+
+    /// Creates a span covering both this span and another
+    pub fn merge(&self, other: &Span) -> Span {
+        if *self == Span::default() {
+            return *other;
+        }
+        if *other == Span::default() {
+            return *self;
+        }
+        let first: &Span = if self.start <= other.start { self } else { other };
+        Span {
+            start: first.start,
+            end: self.end.max(other.end),
+            line: first.line,
+            column: first.column,
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
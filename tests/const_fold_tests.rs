@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use compiler_core::frontend::{
+    ast::{
+        ast_struct::{ASTNode, ModAST, ModElement, AST},
+        data_type::DataType,
+        sem_rule::RulesConfig,
+        syntax_element::SyntaxElement,
+    },
+    const_fold::const_fold_core::ConstFold,
+    lexer::lexer_core::Lexer,
+    parser::parser_core::Parser,
+    sem_analysis::sem_analysis_core::SemAnalysis,
+    symbol_table::symbol_table_struct::SymbolTableStack,
+    utils::error::ErrorType,
+};
+
+/// cargo test --test const_fold_tests
+
+/// Runs semantic analysis and constant folding on a module made from a single source string
+fn fold(source: &str) -> Result<ModAST, Vec<ErrorType>> {
+    let tokens = Lexer::lex_with_spans(source, 1, 0).expect("Failed to lex");
+    let ast: AST = Parser::parse_with_spans(tokens).expect("Failed to parse");
+    let (ast, symbol_table_stack) = SymbolTableStack::gen_sym_table_stack(ast).expect("Failed to generate symbol tables");
+
+    let mut mod_ast: ModAST = ModAST::new();
+    mod_ast.add_child(ModElement::new(ast, Arc::new(Mutex::new(symbol_table_stack)), 0));
+    let analyzed: ModAST = SemAnalysis::sem_analysis(mod_ast, RulesConfig::new(HashMap::new())).expect("Failed semantic analysis");
+    ConstFold::const_fold(analyzed)
+}
+
+/// Retrieves the body of the first function of a single source string once it has been folded
+fn folded_body(source: &str) -> Vec<ASTNode> {
+    let mut mod_ast: ModAST = fold(source).expect("Failed to fold");
+    let element: ModElement = mod_ast.get_child().expect("Empty module");
+    element.get_ast().get_root().get_children()[0].get_children()
+}
+
+fn literal(data_type: DataType, value: &str) -> SyntaxElement {
+    SyntaxElement::Literal { data_type, value: value.to_string() }
+}
+
+#[test]
+fn test_folds_literal_expressions() {
+    let body: Vec<ASTNode> = folded_body(
+        "fn main(): Integer { let x: Integer = 2 * 3 + 4 - 20 / 3; let b: Boolean = 1 < 2 && !false; \
+            let f: Float = 1.5 * 2.0; let c: Boolean = 'a' == 'b'; let y: I8 = -(100i8 + 27i8); return x; }"
+    );
+    let values: Vec<SyntaxElement> = body.iter().filter_map(|statement| match statement.get_element() {
+        SyntaxElement::Initialization { value, .. } => Some(value.get_element()),
+        _ => None,
+    }).collect();
+
+    assert_eq!(values, vec![
+        literal(DataType::Integer, "4"),
+        literal(DataType::Boolean, "true"),
+        literal(DataType::Float, "3.0"),
+        literal(DataType::Boolean, "false"),
+        literal(DataType::I8, "-127"),
+    ]);
+}
+
+#[test]
+fn test_xor_folds_like_it_is_lowered() {
+    let body: Vec<ASTNode> = folded_body("fn main(): Integer { let x: Integer = 6 ^ 3 + 1 ^ 1; let b: Boolean = 2 ^ 3 == 1; return x; }");
+    let values: Vec<SyntaxElement> = body.iter().filter_map(|statement| match statement.get_element() {
+        SyntaxElement::Initialization { value, .. } => Some(value.get_element()),
+        _ => None,
+    }).collect();
+
+    assert_eq!(values, vec![literal(DataType::Integer, "3"), literal(DataType::Boolean, "true")]);
+}
+
+#[test]
+fn test_leaves_expressions_with_variables() {
+    let body: Vec<ASTNode> = folded_body("fn main(a: Integer): Integer { return a + (1 + 2); }");
+
+    match body[0].get_element() {
+        SyntaxElement::Return { value } => match value.get_element() {
+            SyntaxElement::BinaryExpression { left, right, .. } => {
+                assert!(matches!(left.get_element(), SyntaxElement::Variable { name, .. } if name == "a"));
+                assert_eq!(right.get_element(), literal(DataType::Integer, "3"));
+            },
+            _ => panic!("Expected BinaryExpression"),
+        },
+        _ => panic!("Expected Return"),
+    }
+}
+
+#[test]
+fn test_division_by_constant_zero() {
+    let errors: Vec<ErrorType> = fold("fn main(a: Integer): Integer { let x: Integer = a / 0; let y: Float = 1.0 / 0.0; return 7 % (1 - 1); }")
+        .err().unwrap_or_default();
+
+    assert!(matches!(&errors[..], [
+        ErrorType::DivisionByZero { operation: first, .. },
+        ErrorType::DivisionByZero { operation: second, .. },
+    ] if first == "a / 0" && second == "7 % 0"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_constant_integer_overflow() {
+    let errors: Vec<ErrorType> = fold("fn main(): U8 { let x: Integer = 9223372036854775807 + 1; let y: U8 = 1u8 - 2u8; return 200u8 + 50u8; }")
+        .err().unwrap_or_default();
+
+    assert!(matches!(&errors[..], [
+        ErrorType::IntegerOverflow { data_type: first, .. },
+        ErrorType::IntegerOverflow { data_type: second, .. },
+    ] if first == "Integer" && second == "U8"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_constant_overflow_beyond_computed_range() {
+    let errors: Vec<ErrorType> = fold("fn main(): U64 { return 18446744073709551615u64 * 18446744073709551615u64; }")
+        .err().unwrap_or_default();
+
+    assert!(matches!(&errors[..], [ErrorType::IntegerOverflow { data_type, .. }] if data_type == "U64"), 
+        "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_constant_branches_are_simplified() {
+    let body: Vec<ASTNode> = folded_body(
+        "fn foo(a: Integer, b: Integer): Boolean { if (false) { a = 1; } while (1 > 2) { b = 2; } \
+            if (1 == 1) { let c: Integer = 3; } if (false) { return false; } else { return true; } }"
+    );
+
+    assert_eq!(body.len(), 2, "Unexpected body {:?}", body);
+    match body[0].get_element() {
+        SyntaxElement::IfStatement { condition, then_branch, else_branch } => {
+            assert_eq!(condition.get_element(), literal(DataType::Boolean, "true"));
+            assert_eq!(then_branch.len(), 1);
+            assert!(else_branch.is_none());
+        },
+        _ => panic!("Expected IfStatement"),
+    }
+    match body[1].get_element() {
+        SyntaxElement::Return { value } => assert_eq!(value.get_element(), literal(DataType::Boolean, "true")),
+        _ => panic!("Expected Return"),
+    }
+}
@@ -1,24 +1,90 @@
-use std::sync::{Arc, Mutex};
+use std::{collections::HashMap, ffi::CStr, sync::{Arc, Mutex}};
 
 use compiler_core::{
     frontend::{
         ast::{
-            syntax_element::SyntaxElement, 
+            syntax_element::{SyntaxElement, FunctionParameter}, 
             ast_struct::{
                 ASTNode, AST, ModElement, ModAST
             }, 
-            data_type::DataType
+            data_type::DataType,
+            sem_rule::RulesConfig,
         }, 
-        symbol_table::symbol_table_struct::{SymbolTableStack, SymbolTable},
+        sem_analysis::sem_analysis_core::SemAnalysis,
+        symbol_table::symbol_table_struct::{SymbolTableStack, SymbolTable, SymbolInfo, SymbolValue},
+        lexer::lexer_core::Lexer,
+        parser::parser_core::Parser,
     }, 
-    backend::{codegen::ir::ir_codegen_core::IRGenerator, llvm_lib::ir_lib::utils::write_to_file}
+    backend::{
+        codegen::ir::ir_codegen_core::IRGenerator, 
+        llvm_lib::ir_lib::utils::write_to_file,
+    }
 }
 ;
+use llvm_sys::{analysis, core, prelude::LLVMModuleRef};
+
+/// Prints an LLVM module to a string
+fn module_to_string(module: LLVMModuleRef) -> String {
+    unsafe {
+        let ir_ptr = core::LLVMPrintModuleToString(module);
+        let ir: String = CStr::from_ptr(ir_ptr).to_string_lossy().into_owned();
+        core::LLVMDisposeMessage(ir_ptr);
+        ir
+    }
+}
+
+/// Checks that an LLVM module is well formed, returning LLVM's description of the problem if it isn't
+fn verify_module(module: LLVMModuleRef) -> Result<(), String> {
+    let mut message: *mut std::os::raw::c_char = std::ptr::null_mut();
+    unsafe {
+        let failed = analysis::LLVMVerifyModule(
+            module, 
+            analysis::LLVMVerifierFailureAction::LLVMReturnStatusAction, 
+            &mut message
+        );
+        let description: String = if message.is_null() {
+            String::new()
+        } else {
+            let description = CStr::from_ptr(message).to_string_lossy().into_owned();
+            core::LLVMDisposeMessage(message);
+            description
+        };
+        if failed == 0 {
+            Ok(())
+        } else {
+            Err(description)
+        }
+    }
+}
 
 fn create_ast_node(element: SyntaxElement) -> ASTNode {
     ASTNode::new(element)
 }
 
+/// Lexes, parses and builds the symbol tables for a source string, then generates a module from it
+fn generate_module(source: &str, symbol_table_stack_setup: impl FnOnce(&mut SymbolTableStack)) -> LLVMModuleRef {
+    let tokens = Lexer::lex(source).expect("Failed to lex");
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+    let (ast, mut symbol_table_stack) = SymbolTableStack::gen_sym_table_stack(ast).expect("Failed to generate symbol tables");
+    symbol_table_stack_setup(&mut symbol_table_stack);
+
+    let mut mod_ast: ModAST = ModAST::new();
+    mod_ast.add_child(ModElement::new(ast, Arc::new(Mutex::new(symbol_table_stack)), 0));
+    IRGenerator::generate_ir(mod_ast)
+}
+
+/// Generates a module from a source string after it has passed semantic analysis, which resolves its types
+fn generate_checked_module(source: &str) -> LLVMModuleRef {
+    let tokens = Lexer::lex(source).expect("Failed to lex");
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+    let (ast, symbol_table_stack) = SymbolTableStack::gen_sym_table_stack(ast).expect("Failed to generate symbol tables");
+
+    let mut mod_ast: ModAST = ModAST::new();
+    mod_ast.add_child(ModElement::new(ast, Arc::new(Mutex::new(symbol_table_stack)), 0));
+    let mod_ast: ModAST = SemAnalysis::sem_analysis(mod_ast, RulesConfig::new(HashMap::new())).expect("Failed semantic analysis");
+    IRGenerator::generate_ir(mod_ast)
+}
+
 #[test]
 fn test_function_declaration() {
     let symbol_table_stack: Arc<Mutex<SymbolTableStack>> = Arc::new(Mutex::new(SymbolTableStack::new()));
@@ -208,3 +274,324 @@ fn test_function_with_do_while_loop() {
     }
 }
 
+
+#[test]
+fn test_function_call_to_later_function() {
+    let module: LLVMModuleRef = generate_module(
+        "fn main(): Integer { return add(1, 2); } fn add(a: Integer, b: Integer): Integer { return 3; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("%calltmp = call i64 @add(i64 1, i64 2)"), "{}", ir);
+    assert!(ir.contains("ret i64 %calltmp"), "{}", ir);
+}
+
+#[test]
+fn test_void_function_call_declared_from_symbol_table() {
+    let module: LLVMModuleRef = generate_module("fn main(): Integer { log(1 + 2); return 0; }", |stack| {
+        let global_table = stack.get_elements().first().expect("Missing global scope").clone();
+        global_table.lock().unwrap().add("log".to_string(), SymbolInfo::new(
+            DataType::Function,
+            SymbolValue::FunctionValue { 
+                parameters: vec![FunctionParameter::new("value".to_string(), DataType::Integer)], 
+                return_type: None,
+            },
+        ));
+    });
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("declare void @log(i64)"), "{}", ir);
+    assert!(ir.contains("call void @log(i64 3)"), "{}", ir);
+}
+
+#[test]
+#[should_panic(expected = "Function add expects 2 arguments but 1 were given")]
+fn test_function_call_arity_mismatch() {
+    generate_module("fn add(a: Integer, b: Integer): Integer { return 3; } fn main(): Integer { return add(1); }", |_| {});
+}
+
+#[test]
+fn test_local_variables_use_stack_slots() {
+    let module: LLVMModuleRef = generate_module(
+        "fn main(): Integer { let x: Integer = 1; let y: Integer = x + 2; x = y * 3; return x; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("%x = alloca i64"), "{}", ir);
+    assert!(ir.contains("%y = alloca i64"), "{}", ir);
+    assert!(ir.contains("store i64 1, "), "{}", ir);
+    assert!(ir.contains("%x1 = load i64, "), "{}", ir);
+    assert!(ir.contains("%multmp = mul i64 %y2, 3"), "{}", ir);
+}
+
+#[test]
+fn test_unannotated_variables_take_value_type() {
+    let module: LLVMModuleRef = generate_module("fn main(): Integer { let x = 1.5; let y = 2; return y; }", |_| {});
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("%x = alloca double"), "{}", ir);
+    assert!(ir.contains("%y = alloca i64"), "{}", ir);
+}
+
+#[test]
+fn test_block_variables_are_scoped() {
+    let module: LLVMModuleRef = generate_module(
+        "fn main(): Integer { let x: Integer = 1; if (true) { let x: Boolean = false; x = true; } return x; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("alloca i64"), "{}", ir);
+    assert!(ir.contains("alloca i1"), "{}", ir);
+    assert!(ir.contains("store i1 true, "), "{}", ir);
+    assert!(ir.contains("load i64, "), "{}", ir);
+}
+
+#[test]
+fn test_function_parameters_are_bound() {
+    let module: LLVMModuleRef = generate_module(
+        "fn add(a: Integer, b: Integer): Integer { let sum: Integer = a + b; a = 0; return sum; } fn main(): Integer { return add(1, 2); }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("define i64 @add(i64 %a, i64 %b)"), "{}", ir);
+    assert!(ir.contains("%a.addr = alloca i64"), "{}", ir);
+    assert!(ir.contains("%b.addr = alloca i64"), "{}", ir);
+    assert!(ir.contains("store i64 %a, "), "{}", ir);
+    assert!(ir.contains("store i64 %b, "), "{}", ir);
+    assert!(ir.contains("store i64 0, "), "{}", ir);
+}
+
+#[test]
+fn test_void_function_gets_implicit_return() {
+    let module: LLVMModuleRef = generate_module(
+        "fn tick(n: Integer): Void { if (n > 0) { return; } n = 1; } fn main(): Integer { tick(1); return 0; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("define void @tick(i64 %n)"), "{}", ir);
+    assert_eq!(ir.matches("ret void").count(), 2, "{}", ir);
+}
+
+#[test]
+fn test_returning_branches_are_terminated() {
+    let module: LLVMModuleRef = generate_module(
+        "fn pick(flag: Boolean): Integer { if (flag) { return 1; } if (flag) { return 2; } else { return 3; } }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("unreachable"), "{}", ir);
+}
+
+#[test]
+fn test_break_and_continue_target_enclosing_loop() {
+    let module: LLVMModuleRef = generate_module(
+        "fn count(n: Integer): Integer { while (n > 0) { n = n - 1; if (n == 5) { continue; } do { break; } while (true); if (n == 2) { break; } } return n; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("br label %while_cond"), "{}", ir);
+    assert!(ir.contains("br label %while_end"), "{}", ir);
+    assert!(ir.contains("br label %do_end"), "{}", ir);
+}
+
+#[test]
+fn test_break_out_of_returning_do_while() {
+    let module: LLVMModuleRef = generate_module(
+        "fn f(a: Boolean): Integer { do { if (a) { break; } return 1; } while (true); return 2; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("br label %do_end"), "{}", ir);
+    assert!(ir.contains("ret i64 2"), "{}", ir);
+    assert!(!ir.contains("unreachable"), "{}", ir);
+}
+
+#[test]
+fn test_enum_variants_are_tags() {
+    let module: LLVMModuleRef = generate_module(
+        "fn main(): Boolean { let c: Color = Color::Green; return is_red(c); } enum Color [Red, Green] fn is_red(c: Color): Boolean { return c == Color::Red; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("define i1 @is_red(i32 %c)"), "{}", ir);
+    assert!(ir.contains("store i32 1, "), "{}", ir);
+    assert!(ir.contains("icmp eq i32 %c1, 0"), "{}", ir);
+}
+
+#[test]
+fn test_struct_fields_are_geps() {
+    let module: LLVMModuleRef = generate_module(
+        "fn main(): Integer { let p: Point = Point { y: 2, x: 1 }; p.x = p.y; return p.x; } struct Point [x: Integer, y: Integer]", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("%Point = type { i64, i64 }"), "{}", ir);
+    assert!(ir.contains("store %Point { i64 1, i64 2 }, "), "{}", ir);
+    assert!(ir.contains("%x.ptr = getelementptr inbounds %Point, "), "{}", ir);
+    assert!(ir.contains("store i64 %y, "), "{}", ir);
+}
+
+#[test]
+fn test_match_is_switch() {
+    let module: LLVMModuleRef = generate_module(
+        "fn classify(x: Integer): Integer { let y: Integer = 0; match x { 1 => { y = 10; }, 2 => { return 20; }, _ => { y = 30; } } return y; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("switch i64 %x1, label %match_default ["), "{}", ir);
+    assert!(ir.contains("i64 1, label %match_arm"), "{}", ir);
+    assert!(ir.contains("i64 2, label %match_arm"), "{}", ir);
+    assert!(ir.contains("br label %match_end"), "{}", ir);
+}
+
+#[test]
+fn test_elif_chain_branches() {
+    let module: LLVMModuleRef = generate_module(
+        "fn sign(x: Integer): Integer { if (x > 0) { return 1; } elif (x < 0) { return -1; } else { return 0; } }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert_eq!(ir.matches("br i1").count(), 2, "{}", ir);
+    assert!(ir.contains("ret i64 -1"), "{}", ir);
+}
+
+#[test]
+fn test_for_loop_initializer() {
+    let module: LLVMModuleRef = generate_module(
+        "fn sum(): Integer { let total: Integer = 0; for (let i: Integer = 0; i < 10; i = i + 1;) { total = total + i; } \
+            for (let i: Integer = 5; i > 0; i = i - 1;) { total = total + i; } return total; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    // each loop keeps its counter in its own slot, and its condition reads that slot
+    assert!(ir.contains("%i = alloca i64"), "{}", ir);
+    assert!(ir.contains("%i10 = alloca i64"), "{}", ir);
+    assert!(block(&ir, "for_cond").contains("load i64, i64* %i,"), "{}", ir);
+    assert!(block(&ir, "for_cond6").contains("load i64, i64* %i10,"), "{}", ir);
+    assert!(block(&ir, "for_end").contains("store i64 5, i64* %i10"), "{}", ir);
+}
+
+/// Retrieves the instructions of a labelled basic block from printed IR
+fn block<'a>(ir: &'a str, label: &str) -> &'a str {
+    let start: usize = ir.find(&format!("\n{}:", label)).unwrap_or_else(|| panic!("No block {} in {}", label, ir));
+    let rest: &str = &ir[start + 1..];
+    &rest[..rest.find("\n\n").unwrap_or(rest.len())]
+}
+
+#[test]
+fn test_compound_assignment_loads_and_stores() {
+    let module: LLVMModuleRef = generate_module(
+        "fn scale(x: Integer): Integer { x *= 3; x -= 1; return x; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("mul i64 %x1, 3"), "{}", ir);
+    assert!(ir.contains("sub i64 %x2, 1"), "{}", ir);
+    assert_eq!(ir.matches("store i64").count(), 3, "{}", ir);
+}
+
+#[test]
+fn test_float_arithmetic_is_double() {
+    let module: LLVMModuleRef = generate_module(
+        "fn scale(x: Float): Float { let y: Float = x * 2.5; if (y < 1e-3) { return -y; } return y / x - 0.5; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("define double @scale(double %x)"), "{}", ir);
+    assert!(ir.contains("fmul double %x1, 2.500000e+00"), "{}", ir);
+    assert!(ir.contains("fcmp olt double %y2, 1.000000e-03"), "{}", ir);
+    assert!(ir.contains("fneg double"), "{}", ir);
+    assert!(ir.contains("fdiv double"), "{}", ir);
+    assert!(ir.contains("fsub double"), "{}", ir);
+}
+
+#[test]
+fn test_string_literals_are_private_globals() {
+    let module: LLVMModuleRef = generate_module(
+        "fn greeting(): String { let c: Char = 'A'; let s: String = \"a\\u{0}b\"; return s; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("private unnamed_addr constant [4 x i8] c\"a\\00b\\00\""), "{}", ir);
+    assert!(ir.contains("store i32 65"), "{}", ir);
+}
+
+#[test]
+fn test_unsigned_integers_use_unsigned_instructions() {
+    let module: LLVMModuleRef = generate_module(
+        "fn below(a: U32, b: U32): Boolean { let q: U32 = a / b; return q % 10u32 < a; } \
+            fn halve(a: I8): I8 { if (a >= 0i8) { return a / 2i8; } return a; }", 
+        |_| {}
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("define i1 @below(i32 %a, i32 %b)"), "{}", ir);
+    assert!(ir.contains("udiv i32"), "{}", ir);
+    assert!(ir.contains("urem i32 %q"), "{}", ir);
+    assert!(ir.contains("icmp ult i32"), "{}", ir);
+    assert!(ir.contains("define i8 @halve(i8 %a)"), "{}", ir);
+    assert!(ir.contains("icmp sge i8"), "{}", ir);
+    assert!(ir.contains("sdiv i8"), "{}", ir);
+}
+
+#[test]
+fn test_unsuffixed_literals_take_sized_types() {
+    let module: LLVMModuleRef = generate_checked_module(
+        "fn clamp(a: U8, limit: U8): U8 { if (a > 200) { return limit; } return a + 1; } \
+            fn main(): U8 { let b: U8 = 7; b = 9; let c: I16 = -3; return clamp(b, 250); }"
+    );
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("icmp ugt i8 %a1, -56"), "{}", ir);
+    assert!(ir.contains("add i8 %a3, 1"), "{}", ir);
+    assert!(ir.contains("store i8 7, i8* %b"), "{}", ir);
+    assert!(ir.contains("store i8 9, i8* %b"), "{}", ir);
+    assert!(ir.contains("store i16 -3, i16* %c"), "{}", ir);
+    assert!(ir.contains("call i8 @clamp(i8 %b1, i8 -6)"), "{}", ir);
+}
+
+#[test]
+fn test_literal_arithmetic_takes_sized_type() {
+    let module: LLVMModuleRef = generate_checked_module("fn main(): I32 { let a: U8 = 1 + 2; let b: I32 = -1 * 3; return b; }");
+
+    verify_module(module).expect("Generated module is invalid");
+    let ir: String = module_to_string(module);
+    assert!(ir.contains("store i8 3, i8* %a"), "{}", ir);
+    assert!(ir.contains("store i32 -3, i32* %b"), "{}", ir);
+}
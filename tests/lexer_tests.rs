@@ -3,7 +3,7 @@ mod ir_tests;
 use compiler_core::frontend::{
     lexer::token::*,
     lexer::lexer_core::*,
-    utils::error::*,
+    utils::{error::*, span::Span},
 };
 
 /// cargo test --test lexer_tests
@@ -96,7 +96,7 @@ fn test_whitespace_handling() {
 fn test_invalid_char() {
     let input = "let $invalid = 5;";
     let result = Lexer::lex(input);
-    let expected_error = ErrorType::UnrecognizedToken{token: "$".to_string()};
+    let expected_error = ErrorType::UnrecognizedToken{token: "$".to_string(), span: Span::new(4, 5, 1, 5)};
     let expected = Err(vec![expected_error]);
     assert_eq!(result, expected);
 }
@@ -261,7 +261,7 @@ fn test_arithmetic_operators() {
     let result = Lexer::lex(input);
     let expected = vec![
         Token::PLUS, Token::MINUS, Token::MULTIPLY,
-        Token::DIVIDE, Token::MOD, Token::EXPONENT,
+        Token::DIVIDE, Token::MOD, Token::BITWISEXOR,
         Token::EOF,
     ];
     assert_eq!(result, Ok(expected));
@@ -334,3 +334,178 @@ fn test_dot_and_coloncolon_operators() {
     ];
     assert_eq!(result, Ok(expected));
 }
+
+#[test]
+fn test_compound_assignment_operators() {
+    let input = "+= -= *= /= %= a-=1";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::PLUSASSIGN, Token::MINUSASSIGN, Token::MULTIPLYASSIGN, Token::DIVIDEASSIGN, Token::MODASSIGN,
+        Token::IDENTIFIER(vec!['a']), Token::MINUSASSIGN, Token::INT(vec!['1']), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_float_literals() {
+    let input = "3.14 1e-3 2.5E+2 7 4.x";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::FLOAT(vec!['3', '.', '1', '4']), Token::FLOAT(vec!['1', 'e', '-', '3']), 
+        Token::FLOAT(vec!['2', '.', '5', 'e', '+', '2']), Token::INT(vec!['7']), 
+        Token::INT(vec!['4']), Token::DOT, Token::IDENTIFIER(vec!['x']), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_logical_operators_without_whitespace() {
+    let input = "a&&b||c";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::IDENTIFIER(vec!['a']), Token::LOGICALAND, Token::IDENTIFIER(vec!['b']),
+        Token::LOGICALOR, Token::IDENTIFIER(vec!['c']), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_token_spans() {
+    let input = "let x = 10;\n  return x;";
+    let result = Lexer::lex_with_spans(input, 1, 0).expect("Failed to lex");
+    let spans: Vec<Span> = result.iter().map(|token| token.get_span()).collect();
+    let expected = vec![
+        Span::new(0, 3, 1, 1),
+        Span::new(4, 5, 1, 5),
+        Span::new(6, 7, 1, 7),
+        Span::new(8, 10, 1, 9),
+        Span::new(10, 11, 1, 11),
+        Span::new(14, 20, 2, 3),
+        Span::new(21, 22, 2, 10),
+        Span::new(22, 23, 2, 11),
+        Span::new(23, 23, 2, 12),
+    ];
+    assert_eq!(spans, expected);
+}
+
+#[test]
+fn test_token_spans_with_starting_position() {
+    let input = "fn";
+    let result = Lexer::lex_with_spans(input, 7, 120).expect("Failed to lex");
+    assert_eq!(result.first().unwrap().get_token(), Token::FUNCTION);
+    assert_eq!(result.first().unwrap().get_span(), Span::new(120, 122, 7, 1));
+}
+
+#[test]
+fn test_error_span_on_later_line() {
+    let input = "let a = 1;\nlet b = #;";
+    let result = Lexer::lex_with_spans(input, 1, 0);
+    let expected_error = ErrorType::UnrecognizedToken{token: "#".to_string(), span: Span::new(19, 20, 2, 9)};
+    assert_eq!(result, Err(vec![expected_error]));
+}
+
+#[test]
+fn test_string_and_char_literals() {
+    let input = r#"let s = "hi there"; let c = 'x';"#;
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::LET, Token::IDENTIFIER(vec!['s']), Token::EQUAL, Token::STRING("hi there".chars().collect()), Token::SEMICOLON,
+        Token::LET, Token::IDENTIFIER(vec!['c']), Token::EQUAL, Token::CHAR('x'), Token::SEMICOLON, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_escape_sequences() {
+    let input = r#""a\n\t\"\\\u{41}" '\''"#;
+    let result = Lexer::lex(input);
+    let expected = vec![Token::STRING("a\n\t\"\\A".chars().collect()), Token::CHAR('\''), Token::EOF];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_invalid_escape_sequence() {
+    let input = r#"let s = "a\q";"#;
+    let result = Lexer::lex_with_spans(input, 1, 0);
+    let expected_error = ErrorType::InvalidEscape{sequence: "\\q".to_string(), span: Span::new(8, 13, 1, 9)};
+    assert_eq!(result, Err(vec![expected_error]));
+}
+
+#[test]
+fn test_unterminated_string() {
+    let input = "let s = \"abc;\nlet t = 1;";
+    let result = Lexer::lex_with_spans(input, 1, 0);
+    let expected_error = ErrorType::UnterminatedLiteral{span: Span::new(8, 13, 1, 9)};
+    assert_eq!(result, Err(vec![expected_error]));
+}
+
+#[test]
+fn test_comments_are_skipped() {
+    let input = "let a = 1; // trailing comment\n/* block /* nested */ still comment */ let b = a / 2; /// doc";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::LET, Token::IDENTIFIER(vec!['a']), Token::EQUAL, Token::INT(vec!['1']), Token::SEMICOLON,
+        Token::LET, Token::IDENTIFIER(vec!['b']), Token::EQUAL, Token::IDENTIFIER(vec!['a']), Token::DIVIDE, Token::INT(vec!['2']), 
+        Token::SEMICOLON, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_doc_comments_are_kept() {
+    let input = "/// Adds one\n///\n//// not a doc comment\nfn";
+    let result: Vec<Token> = Lexer::lex_with_doc_comments(input, 1, 0).expect("Failed to lex")
+        .into_iter().map(|token| token.get_token()).collect();
+    let expected = vec![
+        Token::DOCCOMMENT("Adds one".chars().collect()), Token::DOCCOMMENT(vec![]), Token::FUNCTION, Token::EOF,
+    ];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_unterminated_block_comment() {
+    let input = "let a = 1;\n/* open /* nested */";
+    let result = Lexer::lex_with_spans(input, 1, 0);
+    let expected_error = ErrorType::UnterminatedComment{span: Span::new(11, 31, 2, 1)};
+    assert_eq!(result, Err(vec![expected_error]));
+}
+
+#[test]
+fn test_radix_literals_and_separators() {
+    let input = "0xFF 0o17 0b1010 1_000_000 0x_dead_BEEF 2.5_0";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::INT("255".chars().collect()), Token::INT("15".chars().collect()), Token::INT("10".chars().collect()),
+        Token::INT("1000000".chars().collect()), Token::INT("3735928559".chars().collect()), Token::FLOAT("2.50".chars().collect()), 
+        Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_integer_suffixes() {
+    let input = "let x: U8 = 0xFFu8 + 1_i16 + 7i64;";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::LET, Token::IDENTIFIER(vec!['x']), Token::COLON, Token::TU8, Token::EQUAL, 
+        Token::TYPEDINT("255".chars().collect(), vec!['u', '8']), Token::PLUS, 
+        Token::TYPEDINT(vec!['1'], vec!['i', '1', '6']), Token::PLUS, 
+        Token::TYPEDINT(vec!['7'], vec!['i', '6', '4']), Token::SEMICOLON, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_invalid_number_literals() {
+    let input = "0b102 256u8 128i8 5i7 0x 9223372036854775808";
+    let result = Lexer::lex_with_spans(input, 1, 0);
+    let expected_errors = vec![
+        ErrorType::InvalidLiteral{literal: "0b102".to_string(), span: Span::new(0, 5, 1, 1)},
+        ErrorType::InvalidLiteral{literal: "256u8".to_string(), span: Span::new(6, 11, 1, 7)},
+        ErrorType::InvalidLiteral{literal: "128i8".to_string(), span: Span::new(12, 17, 1, 13)},
+        ErrorType::InvalidLiteral{literal: "5i7".to_string(), span: Span::new(18, 21, 1, 19)},
+        ErrorType::InvalidLiteral{literal: "0x".to_string(), span: Span::new(22, 24, 1, 23)},
+        ErrorType::InvalidLiteral{literal: "9223372036854775808".to_string(), span: Span::new(25, 44, 1, 26)},
+    ];
+    assert_eq!(result, Err(expected_errors));
+}
@@ -4,8 +4,9 @@ use compiler_core::frontend::{
         data_type::*,
         syntax_element::*,
     },
-    lexer::token::*,
+    lexer::{lexer_core::Lexer, token::*},
     parser::parser_core::*,
+    utils::{error::ErrorType, span::Span},
 };
 
 /// cargo test --test parser_tests
@@ -405,3 +406,456 @@ fn test_function_with_if_else_statement() {
         _ => panic!("Expected IfStatement"),
     }
 }
+
+#[test]
+fn test_node_spans() {
+    let tokens: Vec<SpannedToken> = Lexer::lex_with_spans("fn foo() {\n    let x: Integer = 5;\n}", 3, 40).expect("Failed to lex");
+    let ast = Parser::parse_with_spans(tokens).expect("Failed to parse");
+
+    let function = ast.get_root().get_children().first().unwrap().clone();
+    assert_eq!(function.get_span(), Span::new(40, 76, 3, 1));
+
+    let initialization = function.get_children().first().unwrap().clone();
+    assert_eq!(initialization.get_span(), Span::new(55, 73, 4, 5));
+    match initialization.get_element() {
+        SyntaxElement::Initialization { value, .. } => {
+            assert_eq!(value.get_span(), Span::new(72, 73, 4, 22));
+        },
+        _ => panic!("Expected Initialization"),
+    }
+}
+
+#[test]
+fn test_syntax_error_is_returned() {
+    let tokens: Vec<Token> = Lexer::lex("fn foo() { let x: Integer 5; }").expect("Failed to lex");
+    let errors: Vec<ErrorType> = Parser::parse(tokens).expect_err("Expected a syntax error");
+
+    assert_eq!(errors, vec![ErrorType::SyntaxError {
+        expected: "'='".to_string(),
+        found: "'5'".to_string(),
+        span: Span::default(),
+    }]);
+}
+
+#[test]
+fn test_multiple_syntax_errors_are_reported() {
+    let source: &str = "fn foo() {\n    let x: Integer = ;\n    let y: Boolean = true;\n}\nfn bar( {\n    return 1;\n}";
+    let tokens: Vec<SpannedToken> = Lexer::lex_with_spans(source, 1, 0).expect("Failed to lex");
+    let errors: Vec<ErrorType> = Parser::parse_with_spans(tokens).expect_err("Expected syntax errors");
+
+    assert_eq!(errors, vec![
+        ErrorType::SyntaxError {
+            expected: "an expression".to_string(),
+            found: "';'".to_string(),
+            span: Span::new(32, 33, 2, 22),
+        },
+        ErrorType::SyntaxError {
+            expected: "a parameter or ')'".to_string(),
+            found: "'{'".to_string(),
+            span: Span::new(71, 72, 5, 9),
+        },
+    ]);
+}
+
+#[test]
+fn test_unexpected_end_of_file() {
+    let tokens: Vec<Token> = Lexer::lex("fn foo() { let x: Integer = 5;").expect("Failed to lex");
+    let errors: Vec<ErrorType> = Parser::parse(tokens).expect_err("Expected a syntax error");
+
+    assert_eq!(errors.len(), 1);
+    match &errors[0] {
+        ErrorType::SyntaxError { expected, found, .. } => {
+            assert_eq!(expected, "'}'");
+            assert_eq!(found, "end of file");
+        },
+        _ => panic!("Expected SyntaxError"),
+    }
+}
+
+/// Renders an expression as a fully parenthesized string so the shape of the tree can be compared
+fn expression_to_string(node: &ASTNode) -> String {
+    match node.get_element() {
+        SyntaxElement::BinaryExpression { left, operator, right } => 
+            format!("({} {} {})", expression_to_string(&left), operator, expression_to_string(&right)),
+        SyntaxElement::UnaryExpression { operator, operand } => 
+            format!("({}{})", operator, expression_to_string(&operand)),
+        SyntaxElement::Literal { value, .. } => value,
+        SyntaxElement::Variable { name, .. } => name,
+        other => panic!("Unexpected element in expression: {:?}", other),
+    }
+}
+
+/// Parses `let a: Integer = <expression>;` and returns the initialization value
+fn parse_initialization_value(expression: &str) -> ASTNode {
+    let tokens: Vec<Token> = Lexer::lex(&format!("let a: Integer = {};", expression)).expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+    match ast.get_root().get_children().first().unwrap().get_element() {
+        SyntaxElement::Initialization { value, .. } => *value,
+        _ => panic!("Expected Initialization"),
+    }
+}
+
+#[test]
+fn test_binary_precedence() {
+    assert_eq!(expression_to_string(&parse_initialization_value("1 + 2 * 3")), "(1 + (2 * 3))");
+    assert_eq!(expression_to_string(&parse_initialization_value("1 * 2 + 3 % 4")), "((1 * 2) + (3 % 4))");
+    assert_eq!(expression_to_string(&parse_initialization_value("a + 1 < b * 2 == c >= d")), "(((a + 1) < (b * 2)) == (c >= d))");
+    assert_eq!(expression_to_string(&parse_initialization_value("a || b && c != d")), "(a || (b && (c != d)))");
+    assert_eq!(expression_to_string(&parse_initialization_value("a ^ b + 1 < c ^ d")), "((a ^ (b + 1)) < (c ^ d))");
+}
+
+#[test]
+fn test_binary_associativity() {
+    assert_eq!(expression_to_string(&parse_initialization_value("a - b - c")), "((a - b) - c)");
+    assert_eq!(expression_to_string(&parse_initialization_value("a / b / c")), "((a / b) / c)");
+    assert_eq!(expression_to_string(&parse_initialization_value("a ^ b ^ c")), "((a ^ b) ^ c)");
+}
+
+#[test]
+fn test_parenthesized_and_unary_expressions() {
+    assert_eq!(expression_to_string(&parse_initialization_value("(1 + 2) * 3")), "((1 + 2) * 3)");
+    assert_eq!(expression_to_string(&parse_initialization_value("-a * b")), "((-a) * b)");
+    assert_eq!(expression_to_string(&parse_initialization_value("-a ^ 2")), "((-a) ^ 2)");
+    assert_eq!(expression_to_string(&parse_initialization_value("!a && !(b || c)")), "((!a) && (!(b || c)))");
+    assert_eq!(expression_to_string(&parse_initialization_value("1 <= 2 - -3")), "(1 <= (2 - (-3)))");
+}
+
+#[test]
+fn test_binary_expression_spans() {
+    let tokens: Vec<SpannedToken> = Lexer::lex_with_spans("let a: Integer = 1 + 2 * 3;", 1, 0).expect("Failed to lex");
+    let ast = Parser::parse_with_spans(tokens).expect("Failed to parse");
+
+    match ast.get_root().get_children().first().unwrap().get_element() {
+        SyntaxElement::Initialization { value, .. } => {
+            assert_eq!(value.get_span(), Span::new(17, 26, 1, 18));
+            match value.get_element() {
+                SyntaxElement::BinaryExpression { right, .. } => assert_eq!(right.get_span(), Span::new(21, 26, 1, 22)),
+                _ => panic!("Expected BinaryExpression"),
+            }
+        },
+        _ => panic!("Expected Initialization"),
+    }
+}
+
+#[test]
+fn test_unbalanced_parentheses() {
+    let tokens: Vec<Token> = Lexer::lex("let a: Integer = (1 + 2;").expect("Failed to lex");
+    let errors: Vec<ErrorType> = Parser::parse(tokens).expect_err("Expected a syntax error");
+
+    match &errors[0] {
+        ErrorType::SyntaxError { expected, found, .. } => {
+            assert_eq!(expected, "')'");
+            assert_eq!(found, "';'");
+        },
+        _ => panic!("Expected SyntaxError"),
+    }
+}
+
+#[test]
+fn test_function_call_expression() {
+    let value: ASTNode = parse_initialization_value("foo(a, b + 1, bar()) * 2");
+    match value.get_element() {
+        SyntaxElement::BinaryExpression { left, operator, .. } => {
+            assert_eq!(operator, "*");
+            match left.get_element() {
+                SyntaxElement::FunctionCall { name, arguments } => {
+                    assert_eq!(name, "foo");
+                    assert_eq!(arguments.len(), 3);
+                    assert_eq!(expression_to_string(&arguments[0]), "a");
+                    assert_eq!(expression_to_string(&arguments[1]), "(b + 1)");
+                    match arguments[2].get_element() {
+                        SyntaxElement::FunctionCall { name, arguments } => {
+                            assert_eq!(name, "bar");
+                            assert!(arguments.is_empty());
+                        },
+                        _ => panic!("Expected nested FunctionCall"),
+                    }
+                },
+                _ => panic!("Expected FunctionCall"),
+            }
+        },
+        _ => panic!("Expected BinaryExpression"),
+    }
+}
+
+#[test]
+fn test_function_call_statement() {
+    let tokens: Vec<Token> = Lexer::lex("fn main() { print(1); x = add(1, 2); }").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let function_body = ast.get_root().get_children().first().unwrap().get_children();
+    assert_eq!(function_body.len(), 2);
+    match function_body[0].get_element() {
+        SyntaxElement::FunctionCall { name, arguments } => {
+            assert_eq!(name, "print");
+            assert_eq!(arguments.len(), 1);
+        },
+        _ => panic!("Expected FunctionCall"),
+    }
+    match function_body[1].get_element() {
+        SyntaxElement::Assignment { variable, value } => {
+            assert_eq!(variable, "x");
+            assert!(matches!(value.get_element(), SyntaxElement::FunctionCall { .. }));
+        },
+        _ => panic!("Expected Assignment"),
+    }
+}
+
+#[test]
+fn test_function_call_missing_comma() {
+    let tokens: Vec<Token> = Lexer::lex("foo(a b);").expect("Failed to lex");
+    let errors: Vec<ErrorType> = Parser::parse(tokens).expect_err("Expected a syntax error");
+
+    match &errors[0] {
+        ErrorType::SyntaxError { expected, found, .. } => {
+            assert_eq!(expected, "',' or ')'");
+            assert_eq!(found, "'b'");
+        },
+        _ => panic!("Expected SyntaxError"),
+    }
+}
+
+#[test]
+fn test_initialization_without_annotation() {
+    let tokens: Vec<Token> = Lexer::lex("let x = 3;").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    match ast.get_root().get_children().first().unwrap().get_element() {
+        SyntaxElement::Initialization { variable, data_type, value } => {
+            assert_eq!(variable, "x");
+            assert_eq!(data_type, DataType::Unknown);
+            assert!(matches!(value.get_element(), SyntaxElement::Literal { data_type: DataType::Integer, .. }));
+        },
+        _ => panic!("Expected Initialization"),
+    }
+}
+
+#[test]
+fn test_void_function_and_empty_return() {
+    let tokens: Vec<Token> = Lexer::lex("fn tick(): Void { return; }").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+    let function: ASTNode = ast.get_root().get_children()[0].clone();
+
+    match function.get_element() {
+        SyntaxElement::FunctionDeclaration { return_type, .. } => assert_eq!(return_type, None),
+        _ => panic!("Expected FunctionDeclaration"),
+    }
+    match function.get_children()[0].get_element() {
+        SyntaxElement::Return { value } => assert_eq!(value.get_element(), SyntaxElement::NoExpression),
+        _ => panic!("Expected Return"),
+    }
+}
+
+#[test]
+fn test_enum_variant_path() {
+    let tokens: Vec<Token> = Lexer::lex("fn paint(c: Color): Color { return Color::Red; }").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+    let function: ASTNode = ast.get_root().get_children()[0].clone();
+
+    match function.get_element() {
+        SyntaxElement::FunctionDeclaration { parameters, return_type, .. } => {
+            assert_eq!(parameters[0].get_data_type(), DataType::Named("Color".to_string()));
+            assert_eq!(return_type, Some(DataType::Named("Color".to_string())));
+        },
+        _ => panic!("Expected FunctionDeclaration"),
+    }
+    match function.get_children()[0].get_element() {
+        SyntaxElement::Return { value } => assert_eq!(value.get_element(), SyntaxElement::EnumVariant { 
+            enum_name: "Color".to_string(), 
+            variant: "Red".to_string(),
+        }),
+        _ => panic!("Expected Return"),
+    }
+}
+
+#[test]
+fn test_struct_literal_and_field_access() {
+    let tokens: Vec<Token> = Lexer::lex("fn main(): Integer { let p: Point = Point { x: 1, y: 2 }; p.x = p.y; return p.x; }").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+    let body: Vec<ASTNode> = ast.get_root().get_children()[0].get_children();
+
+    match body[0].get_element() {
+        SyntaxElement::Initialization { value, .. } => match value.get_element() {
+            SyntaxElement::StructLiteral { name, fields } => {
+                assert_eq!(name, "Point");
+                assert_eq!(fields.iter().map(|(field, _)| field.as_str()).collect::<Vec<_>>(), vec!["x", "y"]);
+            },
+            _ => panic!("Expected StructLiteral"),
+        },
+        _ => panic!("Expected Initialization"),
+    }
+    match body[1].get_element() {
+        SyntaxElement::FieldAssignment { base, field, value } => {
+            assert_eq!(field, "x");
+            assert!(matches!(base.get_element(), SyntaxElement::Variable { name, .. } if name == "p"));
+            assert!(matches!(value.get_element(), SyntaxElement::FieldAccess { field, .. } if field == "y"));
+        },
+        _ => panic!("Expected FieldAssignment"),
+    }
+    match body[2].get_element() {
+        SyntaxElement::Return { value } => assert!(matches!(value.get_element(), SyntaxElement::FieldAccess { field, .. } if field == "x")),
+        _ => panic!("Expected Return"),
+    }
+}
+
+#[test]
+fn test_field_of_temporary_is_not_assignable() {
+    let tokens: Vec<Token> = Lexer::lex("fn main(): Integer { make_point().x = 1; return 0; }").expect("Failed to lex");
+    let errors: Vec<ErrorType> = Parser::parse(tokens).expect_err("Expected an invalid assignment");
+
+    assert!(matches!(&errors[..], [ErrorType::InvalidAssignment { target, .. }] if target == "field x of a temporary value"), 
+        "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_match_arms_with_blocks_and_wildcard() {
+    let tokens: Vec<Token> = Lexer::lex("match x { 1 => { let y = 2; print(y); }, _ => { return; } }").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    match ast.get_root().get_children()[0].get_element() {
+        SyntaxElement::MatchStatement { arms, .. } => {
+            assert_eq!(arms.len(), 2);
+            assert!(!arms[0].is_wildcard());
+            assert_eq!(arms[0].get_action().len(), 2);
+            assert!(arms[1].is_wildcard());
+            assert!(matches!(arms[1].get_action()[0].get_element(), SyntaxElement::Return { .. }));
+        },
+        _ => panic!("Expected MatchStatement"),
+    }
+}
+
+#[test]
+fn test_elif_chain_is_nested_if() {
+    let tokens: Vec<Token> = Lexer::lex("if (a) { x = 1; } elif (b) { x = 2; } elif (c) { x = 3; } else { x = 4; }").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut node: ASTNode = ast.get_root().get_children()[0].clone();
+    for expected in ["a", "b", "c"] {
+        node = match node.get_element() {
+            SyntaxElement::IfStatement { condition, else_branch: Some(else_branch), .. } => {
+                assert!(matches!(condition.get_element(), SyntaxElement::Variable { name, .. } if name == expected));
+                assert_eq!(else_branch.len(), 1);
+                else_branch[0].clone()
+            },
+            _ => panic!("Expected IfStatement with an else branch"),
+        };
+    }
+    assert!(matches!(node.get_element(), SyntaxElement::Assignment { variable, .. } if variable == "x"));
+}
+
+#[test]
+fn test_for_loop_initializer() {
+    let tokens: Vec<Token> = Lexer::lex("for (let i: Integer = 0; i < 10; i = i + 1;) { }").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    match ast.get_root().get_children()[0].get_element() {
+        SyntaxElement::ForLoop { initializer: Some(initializer), condition, increment: Some(increment), .. } => {
+            assert!(matches!(initializer.get_element(), SyntaxElement::Initialization { variable, data_type: DataType::Integer, .. } if variable == "i"));
+            assert!(matches!(condition.get_element(), SyntaxElement::BinaryExpression { operator, .. } if operator == "<"));
+            assert!(matches!(increment.get_element(), SyntaxElement::Assignment { variable, .. } if variable == "i"));
+        },
+        _ => panic!("Expected ForLoop with an initializer and increment"),
+    }
+}
+
+#[test]
+fn test_compound_assignment_is_desugared() {
+    let tokens: Vec<Token> = Lexer::lex("x += 1; p.y *= 2;").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+    let statements: Vec<ASTNode> = ast.get_root().get_children();
+
+    match statements[0].get_element() {
+        SyntaxElement::Assignment { variable, value } => {
+            assert_eq!(variable, "x");
+            match value.get_element() {
+                SyntaxElement::BinaryExpression { left, operator, .. } => {
+                    assert_eq!(operator, "+");
+                    assert!(matches!(left.get_element(), SyntaxElement::Variable { name, .. } if name == "x"));
+                },
+                _ => panic!("Expected BinaryExpression"),
+            }
+        },
+        _ => panic!("Expected Assignment"),
+    }
+    match statements[1].get_element() {
+        SyntaxElement::FieldAssignment { field, value, .. } => {
+            assert_eq!(field, "y");
+            assert!(matches!(value.get_element(), SyntaxElement::BinaryExpression { operator, .. } if operator == "*"));
+        },
+        _ => panic!("Expected FieldAssignment"),
+    }
+}
+
+#[test]
+fn test_float_annotation_and_literal() {
+    let tokens: Vec<Token> = Lexer::lex("let pi: Float = 3.14;").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    match ast.get_root().get_children()[0].get_element() {
+        SyntaxElement::Initialization { data_type, value, .. } => {
+            assert_eq!(data_type, DataType::Float);
+            assert_eq!(value.get_element(), SyntaxElement::Literal { data_type: DataType::Float, value: "3.14".to_string() });
+        },
+        _ => panic!("Expected Initialization"),
+    }
+}
+
+#[test]
+fn test_string_and_char_literals() {
+    let tokens: Vec<Token> = Lexer::lex("let s: String = \"a\\tb\"; let c: Char = '\\n';").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+    let statements = ast.get_root().get_children();
+
+    match statements[0].get_element() {
+        SyntaxElement::Initialization { data_type, value, .. } => {
+            assert_eq!(data_type, DataType::String);
+            assert_eq!(value.get_element(), SyntaxElement::Literal { data_type: DataType::String, value: "a\tb".to_string() });
+        },
+        _ => panic!("Expected Initialization"),
+    }
+    match statements[1].get_element() {
+        SyntaxElement::Initialization { data_type, value, .. } => {
+            assert_eq!(data_type, DataType::Char);
+            assert_eq!(value.get_element(), SyntaxElement::Literal { data_type: DataType::Char, value: "\n".to_string() });
+        },
+        _ => panic!("Expected Initialization"),
+    }
+}
+
+#[test]
+fn test_doc_comments_attach_to_declarations() {
+    let source: &str = "/// A point\n/// in 2D\nstruct Point [ x: Integer ]\n\
+        fn main() { /// nothing to document\n let a = 1; }\n/// Colours\nenum Colour [Red]";
+    let tokens: Vec<SpannedToken> = Lexer::lex_with_doc_comments(source, 1, 0).expect("Failed to lex");
+    let ast = Parser::parse_with_spans(tokens).expect("Failed to parse");
+    let declarations = ast.get_root().get_children();
+
+    assert_eq!(declarations.len(), 3);
+    assert_eq!(declarations[0].get_doc(), Some("A point\nin 2D".to_string()));
+    assert_eq!(declarations[0].get_span().get_line(), 3);
+    assert_eq!(declarations[1].get_doc(), None);
+    assert!(declarations[1].get_children().iter().all(|statement| statement.get_doc().is_none()));
+    assert_eq!(declarations[2].get_doc(), Some("Colours".to_string()));
+}
+
+#[test]
+fn test_sized_integer_annotations_and_suffixes() {
+    let tokens: Vec<Token> = Lexer::lex("let x: U8 = 0xFFu8; let y: I64 = 0b11;").expect("Failed to lex");
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+    let statements = ast.get_root().get_children();
+
+    match statements[0].get_element() {
+        SyntaxElement::Initialization { data_type, value, .. } => {
+            assert_eq!(data_type, DataType::U8);
+            assert_eq!(value.get_element(), SyntaxElement::Literal { data_type: DataType::U8, value: "255".to_string() });
+        },
+        _ => panic!("Expected Initialization"),
+    }
+    match statements[1].get_element() {
+        SyntaxElement::Initialization { data_type, value, .. } => {
+            assert_eq!(data_type, DataType::Integer);
+            assert_eq!(value.get_element(), SyntaxElement::Literal { data_type: DataType::Integer, value: "3".to_string() });
+        },
+        _ => panic!("Expected Initialization"),
+    }
+}
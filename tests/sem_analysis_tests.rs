@@ -3,26 +3,29 @@ use std::sync::{Arc, Mutex};
 
 use compiler_core::frontend::{ 
     ast::ast_struct::{ AST, ASTNode }, 
-    ast::{syntax_element::SyntaxElement, sem_rule::SemanticRule, ast_struct::{ModAST, ModElement}}, 
-    ast::{data_type::DataType, sem_rule::RulesConfig},
+    ast::{syntax_element::{ElementKind, SyntaxElement}, sem_rule::{RuleSet, SemanticRule, Severity}, ast_struct::{ModAST, ModElement}}, 
+    ast::{data_type::DataType, sem_rule::RulesConfig, ast_stitcher::ast_stitch},
     symbol_table::symbol_table_struct::{SymbolTable, SymbolTableStack},
-    utils::error::*,
+    sem_analysis::sem_analysis_core::SemAnalysis,
+    lexer::lexer_core::Lexer,
+    parser::parser_core::Parser,
+    utils::{error::*, span::Span},
 };
 
 fn init() -> RulesConfig {
-    let rule = |element: &SyntaxElement, _symbol_table: &mut SymbolTable| -> Option<ErrorType> {
-        if let SyntaxElement::BinaryExpression { left: _, operator, right } = element {
+    let rule = |node: &ASTNode, _symbol_table_stack: &SymbolTableStack| -> Option<ErrorType> {
+        if let SyntaxElement::BinaryExpression { left: _, operator, right } = node.get_element() {
             if operator == "/" && matches!(right.get_element(), SyntaxElement::Literal { data_type: DataType::Integer, value: ref val } if val == "0") {
-                return Some(ErrorType::DivisionByZero{operation: "Divisor is zero".to_string()});
+                return Some(ErrorType::DivisionByZero{operation: "Divisor is zero".to_string(), span: node.get_span()});
             }
         }
         None
     };
 
-    let sem_rule: SemanticRule = SemanticRule::new(rule);
+    let sem_rule: SemanticRule = SemanticRule::new("division_by_zero", Severity::Error, rule);
     let sem_rules: Vec<SemanticRule> = vec![sem_rule];
-    let mut rule_mapping: HashMap<SyntaxElement, Vec<SemanticRule>> = HashMap::new();
-    rule_mapping.insert(SyntaxElement::BinaryExpression { left: Box::new(ASTNode::default()), operator: String::from("/"), right: Box::new(ASTNode::default()) }, sem_rules);
+    let mut rule_mapping: HashMap<ElementKind, Vec<SemanticRule>> = HashMap::new();
+    rule_mapping.insert(ElementKind::BinaryExpression, sem_rules);
 
     RulesConfig::new(rule_mapping)
 }
@@ -52,15 +55,604 @@ fn basic_test() {
 
     let rules: RulesConfig = init();
 
-    // comment this back in after implementing binary expressions
-    // match SemAnalysis::sem_analysis(mod_ast, rules) {
-    //     Ok(mod_ast) => {
-    //         panic!("expected error")
-    //     }
-    //     Err(errors) => {
-    //         assert!(errors.iter().any(|e| matches!(e, ErrorType::DivisionByZero { .. })),
-    //         "Expected DivisionByZero error, but found {:?}", errors);
-    //     }
-    // }
+    match SemAnalysis::sem_analysis(mod_ast, rules) {
+        Ok(_) => {
+            panic!("expected error")
+        }
+        Err(errors) => {
+            assert!(errors.iter().any(|e| matches!(e, ErrorType::DivisionByZero { .. })),
+            "Expected DivisionByZero error, but found {:?}", errors);
+        }
+    }
+
+}
+
+/// Lexes, parses and builds the symbol tables for a source string
+fn generate_mod_element(source: &str) -> ModElement {
+    let tokens = Lexer::lex_with_spans(source, 1, 0).expect("Failed to lex");
+    let ast: AST = Parser::parse_with_spans(tokens).expect("Failed to parse");
+    let (ast, symbol_table_stack) = SymbolTableStack::gen_sym_table_stack(ast).expect("Failed to generate symbol tables");
+    ModElement::new(ast, Arc::new(Mutex::new(symbol_table_stack)), 0)
+}
+
+/// Runs semantic analysis on a module made from a single source string
+fn run_sem_analysis(source: &str) -> Result<ModAST, Vec<ErrorType>> {
+    let mut mod_ast: ModAST = ModAST::new();
+    mod_ast.add_child(generate_mod_element(source));
+    SemAnalysis::sem_analysis(mod_ast, RulesConfig::new(HashMap::new()))
+}
+
+/// Returns the errors found by semantic analysis of a source string
+fn analyze(source: &str) -> Vec<ErrorType> {
+    run_sem_analysis(source).err().unwrap_or_default()
+}
+
+#[test]
+fn test_well_typed_program() {
+    let source: &str = "fn add(a: Integer, b: Integer): Integer { let sum: Integer = a + b; if (sum > 2 && !false) { sum = sum * 2; } return sum; }";
+    let errors: Vec<ErrorType> = analyze(source);
+    assert!(errors.is_empty(), "Expected no errors, found {:?}", errors);
+}
+
+#[test]
+fn test_initialization_type_mismatch() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Integer { let x: Integer = true; return x; }");
+
+    assert_eq!(errors, vec![ErrorType::TypeMismatch { 
+        left_type: "Integer".to_string(), 
+        right_type: "Boolean".to_string(), 
+        span: Span::new(21, 42, 1, 22),
+    }]);
+}
+
+#[test]
+fn test_binary_operand_mismatch() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Integer { return 1 + true; }");
+
+    assert!(matches!(&errors[..], [ErrorType::TypeMismatch { left_type, right_type, .. }] 
+        if left_type == "Integer" && right_type == "Boolean"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_unsupported_unary_operator() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Boolean { return !5; }");
+
+    assert!(matches!(&errors[..], [ErrorType::UnsupportedOperator { operator, operand_type, .. }] 
+        if operator == "!" && operand_type == "Integer"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_non_boolean_condition() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Integer { while (1) { return 1; } return 0; }");
+
+    assert!(matches!(&errors[..], [ErrorType::TypeMismatch { left_type, right_type, .. }] 
+        if left_type == "Boolean" && right_type == "Integer"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_return_type_mismatch() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Boolean { return 1; }");
+
+    assert!(matches!(&errors[..], [ErrorType::TypeMismatch { left_type, right_type, .. }] 
+        if left_type == "Boolean" && right_type == "Integer"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_undefined_variable() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Integer { return y; }");
+
+    assert_eq!(errors, vec![ErrorType::UndefinedVariable { 
+        variable_name: "y".to_string(), 
+        span: Span::new(28, 29, 1, 29),
+    }]);
+}
+
+#[test]
+fn test_multiple_type_errors_are_reported() {
+    let source: &str = "fn main(): Integer { let x: Integer = false; let y: Boolean = 1 < true; return z; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert_eq!(errors.len(), 3, "Unexpected errors {:?}", errors);
+    assert!(matches!(errors[0], ErrorType::TypeMismatch { .. }));
+    assert!(matches!(errors[1], ErrorType::TypeMismatch { .. }));
+    assert!(matches!(errors[2], ErrorType::UndefinedVariable { .. }));
+}
+
+#[test]
+fn test_inferred_initialization_type() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Integer { let x = 1 < 2; let y: Integer = x; return 0; }");
+
+    assert!(matches!(&errors[..], [ErrorType::TypeMismatch { left_type, right_type, .. }] 
+        if left_type == "Integer" && right_type == "Boolean"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_variable_types_are_resolved() {
+    let mut mod_ast: ModAST = match run_sem_analysis("fn main(flag: Boolean): Integer { let x = 2; if (flag) { let x = flag; } return x; }") {
+        Ok(mod_ast) => mod_ast,
+        Err(errors) => panic!("Expected no errors, found {:?}", errors),
+    };
+    let function: ASTNode = mod_ast.get_child().expect("Expected a module element").get_ast().get_root().get_children()[0].clone();
+    let body: Vec<ASTNode> = function.get_children();
+
+    assert!(matches!(body[0].get_element(), SyntaxElement::Initialization { data_type: DataType::Integer, .. }));
+    match body[1].get_element() {
+        SyntaxElement::IfStatement { condition, then_branch, .. } => {
+            assert!(matches!(condition.get_element(), SyntaxElement::Variable { data_type: DataType::Boolean, .. }));
+            assert!(matches!(then_branch[0].get_element(), SyntaxElement::Initialization { data_type: DataType::Boolean, .. }));
+        },
+        _ => panic!("Expected IfStatement"),
+    }
+    match body[2].get_element() {
+        SyntaxElement::Return { value } => {
+            assert!(matches!(value.get_element(), SyntaxElement::Variable { data_type: DataType::Integer, .. }));
+        },
+        _ => panic!("Expected Return"),
+    }
+}
+
+#[test]
+fn test_function_call_is_checked() {
+    let source: &str = "fn add(a: Integer, b: Integer): Integer { return a + b; } fn main(): Boolean { let sum = add(1, 2); return sum == 3; }";
+    let errors: Vec<ErrorType> = analyze(source);
+    assert!(errors.is_empty(), "Expected no errors, found {:?}", errors);
+}
+
+#[test]
+fn test_undefined_function() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Integer { return missing(1); }");
+
+    assert_eq!(errors, vec![ErrorType::UndefinedFunction { 
+        function_name: "missing".to_string(), 
+        span: Span::new(28, 38, 1, 29),
+    }]);
+}
+
+#[test]
+fn test_function_call_arity_mismatch() {
+    let errors: Vec<ErrorType> = analyze("fn add(a: Integer, b: Integer): Integer { return a + b; } fn main(): Integer { return add(1); }");
+
+    assert!(matches!(&errors[..], [ErrorType::ArityMismatch { function_name, expected: 2, found: 1, .. }] 
+        if function_name == "add"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_function_call_argument_type_mismatch() {
+    let errors: Vec<ErrorType> = analyze("fn neg(a: Integer): Integer { return -a; } fn main(): Integer { return neg(true); }");
+
+    assert!(matches!(&errors[..], [ErrorType::TypeMismatch { left_type, right_type, .. }] 
+        if left_type == "Integer" && right_type == "Boolean"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_call_of_non_function() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Integer { let x: Integer = 1; return x(2); }");
+
+    assert!(matches!(&errors[..], [ErrorType::NotCallable { name, .. }] if name == "x"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_non_value_symbols_are_rejected() {
+    let source: &str = "enum Color [Red] fn one(): Integer { return 1; } \
+        fn main(): Integer { let f = one; let c = Color; return one; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::NotAValue { name: function, .. },
+        ErrorType::NotAValue { name: enum_name, .. },
+        ErrorType::NotAValue { name: returned, .. },
+    ] if function == "one" && enum_name == "Color" && returned == "one"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_call_takes_return_type() {
+    let errors: Vec<ErrorType> = analyze("fn one(): Integer { return 1; } fn main(): Boolean { return one(); }");
+
+    assert!(matches!(&errors[..], [ErrorType::TypeMismatch { left_type, right_type, .. }] 
+        if left_type == "Boolean" && right_type == "Integer"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_calls_across_mod_elements() {
+    let mod_ast: ModAST = ast_stitch(vec![
+        generate_mod_element("fn main(): Integer { return add(1, 2); }"),
+        generate_mod_element("fn add(a: Integer, b: Integer): Integer { return a + b; }"),
+    ]);
+
+    if let Err(errors) = SemAnalysis::sem_analysis(mod_ast, RulesConfig::new(HashMap::new())) {
+        panic!("Expected no errors, found {:?}", errors);
+    }
+}
+
+#[test]
+fn test_returns_on_every_path() {
+    let source: &str = "fn sign(n: Integer): Integer { if (n < 0) { return -1; } else { if (n == 0) { return 0; } } do { return 1; } while (true); } \
+        fn tick(): Void { return; }";
+    let errors: Vec<ErrorType> = analyze(source);
+    assert!(errors.is_empty(), "Expected no errors, found {:?}", errors);
+}
+
+#[test]
+fn test_missing_return() {
+    let errors: Vec<ErrorType> = analyze("fn pick(flag: Boolean): Integer { if (flag) { return 1; } }");
+
+    assert_eq!(errors, vec![ErrorType::MissingReturn { 
+        function_name: "pick".to_string(), 
+        span: Span::new(0, 59, 1, 1),
+    }]);
+}
+
+#[test]
+fn test_loop_exits_before_return() {
+    let errors: Vec<ErrorType> = analyze("fn f(a: Boolean): Integer { do { if (a) { break; } return 1; } while (true); } \
+        fn g(a: Boolean): Integer { do { match a { true => { continue; }, false => {} } return 1; } while (a); }");
+    assert!(matches!(&errors[..], [
+        ErrorType::MissingReturn { function_name: first, .. },
+        ErrorType::MissingReturn { function_name: second, .. },
+    ] if first == "f" && second == "g"), "Unexpected errors {:?}", errors);
+
+    let errors: Vec<ErrorType> = analyze("fn h(a: Boolean): Integer { do { while (a) { break; } return 1; } while (true); }");
+    assert!(errors.is_empty(), "Expected no errors, found {:?}", errors);
+}
+
+#[test]
+fn test_void_return_mismatches() {
+    let errors: Vec<ErrorType> = analyze("fn tick(): Void { return 1; } fn one(): Integer { return; }");
+
+    assert!(matches!(&errors[..], [
+        ErrorType::TypeMismatch { left_type: void_left, right_type: void_right, .. }, 
+        ErrorType::TypeMismatch { left_type: int_left, right_type: int_right, .. },
+    ] if void_left == "None" && void_right == "Integer" && int_left == "Integer" && int_right == "None"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_void_values_are_rejected() {
+    let source: &str = "fn tick(): Void { return; } \
+        fn main(): Integer { let x = tick(); let y: Integer = 1; y = tick(); return y; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert_eq!(errors, vec![
+        ErrorType::VoidValue { span: Span::new(49, 63, 1, 50) },
+        ErrorType::VoidValue { span: Span::new(85, 95, 1, 86) },
+    ]);
+}
+
+#[test]
+fn test_loop_control_inside_loops() {
+    let source: &str = "fn count(n: Integer): Integer { while (n > 0) { n = n - 1; if (n == 5) { continue; } do { break; } while (true); } return n; }";
+    let errors: Vec<ErrorType> = analyze(source);
+    assert!(errors.is_empty(), "Expected no errors, found {:?}", errors);
+}
+
+#[test]
+fn test_loop_control_outside_loops() {
+    let errors: Vec<ErrorType> = analyze("fn main(): Integer { if (true) { break; } continue; return 0; }");
+
+    assert_eq!(errors, vec![
+        ErrorType::OutsideLoop { statement: "break".to_string(), span: Span::new(33, 39, 1, 34) },
+        ErrorType::OutsideLoop { statement: "continue".to_string(), span: Span::new(42, 51, 1, 43) },
+    ]);
+}
+
+#[test]
+fn test_enum_variants_are_checked() {
+    let source: &str = "enum Color [Red, Green] fn is_red(c: Color): Boolean { let red: Color = Color::Red; return c == red; }";
+    let errors: Vec<ErrorType> = analyze(source);
+    assert!(errors.is_empty(), "Expected no errors, found {:?}", errors);
+
+    let errors: Vec<ErrorType> = analyze("enum Color [Red, Green] fn blue(): Color { return Color::Blue; }");
+    assert!(matches!(&errors[..], [ErrorType::UndefinedVariant { enum_name, variant, .. }] 
+        if enum_name == "Color" && variant == "Blue"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_enum_type_errors() {
+    let source: &str = "enum Color [Red, Red] fn main(): Boolean { let s: Shape = 1; return Color::Red == 0; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::DuplicateDefinition { name, .. },
+        ErrorType::UndefinedType { type_name, .. },
+        ErrorType::TypeMismatch { left_type, right_type, .. },
+    ] if name == "Red" && type_name == "Shape" && left_type == "Color" && right_type == "Integer"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_struct_fields_are_checked() {
+    let source: &str = "struct Point [x: Integer, y: Integer] fn main(): Integer { let p = Point { x: 1, y: 2 }; p.y = p.x + 1; return p.y; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(errors.is_empty(), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_struct_field_errors() {
+    let source: &str = "struct Point [x: Integer, y: Integer] fn main(): Integer { let p: Point = Point { x: true, z: 2 }; p.y = false; return p.w; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::TypeMismatch { left_type, right_type, .. },
+        ErrorType::UndefinedField { field: literal_field, .. },
+        ErrorType::MissingField { field: missing_field, .. },
+        ErrorType::TypeMismatch { left_type: assigned_type, .. },
+        ErrorType::UndefinedField { struct_name, field: read_field, .. },
+    ] if left_type == "Integer" && right_type == "Boolean" && literal_field == "z" && missing_field == "y" 
+        && assigned_type == "Integer" && struct_name == "Point" && read_field == "w"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_exhaustive_matches() {
+    let source: &str = "enum Color [Red, Green] \
+        fn name(c: Color): Integer { match c { Color::Red => { return 1; }, Color::Green => { return 2; } } } \
+        fn sign(x: Integer): Integer { match x { 0 => { return 0; }, -1 => { return -1; }, _ => { return 1; } } }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(errors.is_empty(), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_match_errors() {
+    let source: &str = "enum Color [Red, Green, Blue] \
+        fn main(c: Color, x: Integer): Void { match c { Color::Red => { }, Color::Red => { } } match x { _ => { }, 1 => { }, x + 1 => { } } }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::UnreachableArm { .. },
+        ErrorType::NonExhaustiveMatch { missing, .. },
+        ErrorType::UnreachableArm { .. },
+        ErrorType::UnreachableArm { .. },
+    ] if *missing == vec!["Green".to_string(), "Blue".to_string()]), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_elif_chains() {
+    let source: &str = "fn sign(x: Integer): Integer { if (x > 0) { return 1; } elif (x < 0) { return -1; } else { return 0; } } \
+        fn bad(x: Integer): Integer { if (x > 0) { return 1; } elif (x) { return 2; } }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::TypeMismatch { left_type, right_type, .. },
+        ErrorType::MissingReturn { function_name, .. },
+    ] if left_type == "Boolean" && right_type == "Integer" && function_name == "bad"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_for_loop_initializer_is_scoped() {
+    let source: &str = "fn main(): Integer { let total = 0; for (let i = 0; i < 10; i = i + 1;) { total = total + i; } \
+        for (let j: Boolean = 1; j; j = false;) { } return i; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::TypeMismatch { left_type, right_type, .. },
+        ErrorType::UndefinedVariable { variable_name, .. },
+    ] if left_type == "Boolean" && right_type == "Integer" && variable_name == "i"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_compound_assignments() {
+    let source: &str = "fn main(): Integer { let x = 1; x += 2; x %= 3; let b = true; b -= 1; b += true; return x; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::TypeMismatch { left_type, right_type, .. },
+        ErrorType::UnsupportedOperator { operator, operand_type, .. },
+    ] if left_type == "Boolean" && right_type == "Integer" && operator == "+" && operand_type == "Boolean"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_float_arithmetic() {
+    let source: &str = "fn area(r: Float): Float { let a = 3.14 * r * r; if (a > 1e3) { return -a; } return a % 2.0 + 1; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::TypeMismatch { left_type, right_type, .. },
+    ] if left_type == "Float" && right_type == "Integer"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_string_and_char_operations() {
+    let source: &str = "fn main(): Boolean { let s: String = \"hi\"; let c = 'a'; let t = s + \"!\"; let d = c == 1; return c < 'z'; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::UnsupportedOperator { operator, operand_type, .. },
+        ErrorType::TypeMismatch { left_type, right_type, .. },
+    ] if operator == "+" && operand_type == "String" && left_type == "Char" && right_type == "Integer"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_sized_integer_operations() {
+    let source: &str = "fn main(): U8 { let a: U8 = 200u8; let b: I16 = -5i16 * 2i16; let c = a + 1; let d = -a; \
+        let e: U8 = b; match b { 1i16 => { }, _ => { } } return a / 2u8; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert!(matches!(&errors[..], [
+        ErrorType::UnsupportedOperator { operator, operand_type, .. },
+        ErrorType::TypeMismatch { left_type, right_type, .. },
+    ] if operator == "-" && operand_type == "U8" && left_type == "U8" && right_type == "I16"), "Unexpected errors {:?}", errors);
+}
+
+#[test]
+fn test_unsuffixed_literals_take_expected_type() {
+    let source: &str = "fn f(x: I8): I8 { return x; } fn main(): U8 { let a: U8 = 1; a = 255; let b: I8 = -128; \
+        let c = a + 1; match b { -1 => { }, _ => { } } let d: U8 = 256; let e = f(-129); return 1 + a; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert_eq!(errors, vec![
+        ErrorType::IntegerOverflow { data_type: "U8".to_string(), span: Span::new(147, 150, 1, 148) },
+        ErrorType::IntegerOverflow { data_type: "I8".to_string(), span: Span::new(162, 166, 1, 163) },
+    ]);
+}
+
+#[test]
+fn test_literal_arithmetic_takes_expected_type() {
+    let source: &str = "fn main(): U8 { let a: U8 = 1 + 2; let b: I32 = -1; let c: U8 = 1 + 300; let d: U8 = -(1 + 2); return a; }";
+    let errors: Vec<ErrorType> = analyze(source);
+
+    assert_eq!(errors, vec![
+        ErrorType::IntegerOverflow { data_type: "U8".to_string(), span: Span::new(68, 71, 1, 69) },
+        ErrorType::UnsupportedOperator { operator: "-".to_string(), operand_type: "U8".to_string(), span: Span::new(85, 93, 1, 86) },
+    ]);
+}
+
+/// Runs semantic analysis on a single source string with the rules of a configuration file
+fn analyze_with_config(source: &str, config: &str) -> (Vec<ErrorType>, Vec<ErrorType>) {
+    let mut mod_ast: ModAST = ModAST::new();
+    mod_ast.add_child(generate_mod_element(source));
+    let rules: RulesConfig = RulesConfig::from_config(config).expect("Failed to read config");
+    let (result, warnings) = SemAnalysis::sem_analysis_with_warnings(mod_ast, rules);
+    (result.err().unwrap_or_default(), warnings)
+}
+
+#[test]
+fn test_built_in_rules_default_to_warnings() {
+    let (errors, warnings) = analyze_with_config("fn Main(a: Integer): Integer { a = a; if (true) { } return a; }", "");
+
+    assert!(errors.is_empty(), "Expected no errors, found {:?}", errors);
+    assert_eq!(warnings, vec![
+        ErrorType::RuleViolation { rule: "self_assignment".to_string(), message: "a is assigned to itself".to_string(), span: Span::new(31, 36, 1, 32) },
+        ErrorType::RuleViolation { rule: "empty_block".to_string(), message: "block has no statements".to_string(), span: Span::new(38, 51, 1, 39) },
+        ErrorType::RuleViolation { rule: "constant_condition".to_string(), message: "condition is always true".to_string(), span: Span::new(38, 51, 1, 39) },
+    ]);
+}
+
+#[test]
+fn test_config_sets_rule_severities() {
+    let config: &str = "# rules for this test\nself_assignment = error\n\nconstant_condition = off # too noisy\nsnake_case_names = on\n";
+    let (errors, warnings) = analyze_with_config("fn Main(a: Integer): Integer { a = a; if (true) { } return a; }", config);
+
+    assert!(matches!(&errors[..], [ErrorType::RuleViolation { rule, .. }] if rule == "self_assignment"), "Unexpected errors {:?}", errors);
+    let rules: Vec<String> = warnings.iter().filter_map(|warning| match warning {
+        ErrorType::RuleViolation { rule, .. } => Some(rule.clone()),
+        _ => None,
+    }).collect();
+    assert_eq!(rules, vec!["snake_case_names", "empty_block"]);
+}
+
+#[test]
+fn test_invalid_config() {
+    let errors: Vec<ErrorType> = RulesConfig::from_config("empty_block = warning\nmissing_rule = error\nempty_block = loud\nempty_block\n")
+        .err().unwrap_or_default();
+
+    assert_eq!(errors, vec![
+        ErrorType::InvalidConfig { line: 2, message: "unknown rule missing_rule".to_string() },
+        ErrorType::InvalidConfig { line: 3, message: "unknown severity loud".to_string() },
+        ErrorType::InvalidConfig { line: 4, message: "expected `rule = severity`, found `empty_block`".to_string() },
+    ]);
+}
+
+#[test]
+fn test_set_severity_of_named_rule() {
+    let rule = |node: &ASTNode, _symbol_table_stack: &SymbolTableStack| -> Option<ErrorType> {
+        matches!(node.get_element(), SyntaxElement::Break).then(|| ErrorType::DevError {})
+    };
+    let mut rule_mapping: HashMap<ElementKind, Vec<SemanticRule>> = HashMap::new();
+    rule_mapping.insert(ElementKind::Break, vec![SemanticRule::new("no_break", Severity::Error, rule)]);
+    let mut rules: RulesConfig = RulesConfig::new(rule_mapping);
+
+    assert!(rules.set_severity("no_break", Severity::Warning));
+    assert!(!rules.set_severity("missing_rule", Severity::Off));
+    assert!(rules.get_rules(&SyntaxElement::Break).iter().any(|rule| rule.get_name() == "no_break" && rule.get_severity() == Severity::Warning));
+
+    rules.set_severity("no_break", Severity::Off);
+    assert!(rules.get_rules(&SyntaxElement::Break).iter().all(|rule| rule.get_name() != "no_break"));
+}
+
+/// Creates a rule set of project lints that look at whole nodes and the symbol tables in scope
+fn project_lints() -> RuleSet {
+    let mut shadowing: RuleSet = RuleSet::new("shadowing");
+    shadowing.add_rule(ElementKind::Initialization, SemanticRule::new("no_shadowing", Severity::Error, |node, symbol_table_stack| {
+        match node.get_element() {
+            SyntaxElement::Initialization { variable, .. } if symbol_table_stack.lookup(&variable).is_some() =>
+                Some(ErrorType::RuleViolation { rule: "no_shadowing".to_string(), message: format!("{} shadows a variable", variable), span: node.get_span() }),
+            _ => None,
+        }
+    }));
+
+    let mut lints: RuleSet = RuleSet::new("project");
+    lints.add_rule(ElementKind::Literal, SemanticRule::new("no_magic_numbers", Severity::Warning, |node, _| {
+        match node.get_element() {
+            SyntaxElement::Literal { data_type: DataType::Integer, value } if value != "0" && value != "1" =>
+                Some(ErrorType::RuleViolation { rule: "no_magic_numbers".to_string(), message: format!("magic number {}", value), span: node.get_span() }),
+            _ => None,
+        }
+    }));
+    lints.add_rule_set(shadowing);
+    lints
+}
+
+/// Runs semantic analysis on a single source string with the project lints and a configuration file
+fn analyze_with_lints(source: &str, config: &str) -> (Vec<ErrorType>, Vec<ErrorType>) {
+    let mut mod_ast: ModAST = ModAST::new();
+    mod_ast.add_child(generate_mod_element(source));
+    let mut rules: RulesConfig = RulesConfig::new(HashMap::new());
+    rules.add_rule_set(project_lints());
+    rules.apply_config(config).expect("Failed to read config");
+
+    let (result, warnings) = SemAnalysis::sem_analysis_with_warnings(mod_ast, rules);
+    (result.err().unwrap_or_default(), warnings)
+}
+
+#[test]
+fn test_custom_rules_by_kind() {
+    let source: &str = "fn main(a: Integer): Integer { let a: Integer = 42; return a; }";
+    let (errors, warnings) = analyze_with_lints(source, "");
+
+    assert_eq!(errors, vec![ErrorType::RuleViolation {
+        rule: "no_shadowing".to_string(),
+        message: "a shadows a variable".to_string(),
+        span: Span::new(31, 50, 1, 32),
+    }]);
+    assert_eq!(warnings, vec![ErrorType::RuleViolation {
+        rule: "no_magic_numbers".to_string(),
+        message: "magic number 42".to_string(),
+        span: Span::new(48, 50, 1, 49),
+    }]);
+}
+
+#[test]
+fn test_rules_check_nested_expressions() {
+    let source: &str = "fn twice(x: Integer): Integer { return x * 2; } \
+        fn main(a: Integer): Integer { let b: Integer = a - 7; if (b > 1) { b = twice(5); } return twice(twice(b)); }";
+    let mut rules: RulesConfig = RulesConfig::new(HashMap::new());
+    rules.add_rule_set(project_lints());
+    rules.apply_config("shadowing = off").expect("Failed to read config");
+    rules.add_rule(ElementKind::BinaryExpression, SemanticRule::new("no_multiplication", Severity::Warning, |node, _| {
+        match node.get_element() {
+            SyntaxElement::BinaryExpression { operator, .. } if operator == "*" =>
+                Some(ErrorType::RuleViolation { rule: "no_multiplication".to_string(), message: "multiplication".to_string(), span: node.get_span() }),
+            _ => None,
+        }
+    }));
+    rules.add_rule(ElementKind::FunctionCall, SemanticRule::new("no_nested_calls", Severity::Warning, |node, _| {
+        match node.get_element() {
+            SyntaxElement::FunctionCall { name, arguments } if arguments.iter().any(|argument| matches!(argument.get_element(), SyntaxElement::FunctionCall { .. })) =>
+                Some(ErrorType::RuleViolation { rule: "no_nested_calls".to_string(), message: format!("{} of a call", name), span: node.get_span() }),
+            _ => None,
+        }
+    }));
+
+    let mut mod_ast: ModAST = ModAST::new();
+    mod_ast.add_child(generate_mod_element(source));
+    let (result, warnings) = SemAnalysis::sem_analysis_with_warnings(mod_ast, rules);
+    assert!(result.is_ok(), "Unexpected errors {:?}", result.err());
+
+    let messages: Vec<String> = warnings.iter().filter_map(|warning| match warning {
+        ErrorType::RuleViolation { message, .. } => Some(message.clone()),
+        _ => None,
+    }).collect();
+    assert_eq!(messages, vec!["multiplication", "magic number 2", "magic number 7", "magic number 5", "twice of a call"]);
+}
+
+#[test]
+fn test_rule_sets_are_configured_by_name() {
+    let source: &str = "fn main(a: Integer): Integer { let a: Integer = 42; return a; }";
+
+    let (errors, warnings) = analyze_with_lints(source, "project = off\nshadowing = warning");
+    assert!(errors.is_empty(), "Expected no errors, found {:?}", errors);
+    assert!(matches!(&warnings[..], [ErrorType::RuleViolation { rule, .. }] if rule == "no_shadowing"), "Unexpected warnings {:?}", warnings);
 
+    let (errors, warnings) = analyze_with_lints(source, "built_in = off\nno_magic_numbers = error\nno_shadowing = off");
+    assert!(warnings.is_empty(), "Expected no warnings, found {:?}", warnings);
+    assert!(matches!(&errors[..], [ErrorType::RuleViolation { rule, .. }] if rule == "no_magic_numbers"), "Unexpected errors {:?}", errors);
 }